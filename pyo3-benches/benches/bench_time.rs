@@ -0,0 +1,218 @@
+//! Round-trip benchmarks for the `time` conversions.
+//!
+//! The conversions take a different code path when built against the limited API, so the same
+//! benchmarks are meant to be run once per configuration and compared with criterion baselines:
+//!
+//! ```text
+//! cargo bench --bench bench_time -- --save-baseline full
+//! cargo bench --bench bench_time --features abi3 -- --baseline full
+//! ```
+
+use std::hint::black_box;
+
+use codspeed_criterion_compat::{criterion_group, criterion_main, Bencher, Criterion};
+use time::{Date, Duration, Month, OffsetDateTime, PrimitiveDateTime, Time, UtcOffset};
+
+use pyo3::prelude::*;
+use pyo3::time::{durations_to_timedelta_index, extract_primitive_datetimes_from_index};
+use pyo3::types::{IntoPyDict, PyList};
+use pyo3::BoundObject;
+
+const API: &str = if cfg!(Py_LIMITED_API) {
+    "limited"
+} else {
+    "full"
+};
+
+fn date() -> Date {
+    Date::from_calendar_date(2024, Month::February, 29).unwrap()
+}
+
+fn time() -> Time {
+    Time::from_hms_micro(12, 34, 56, 789_012).unwrap()
+}
+
+fn bench_roundtrip<T>(b: &mut Bencher<'_>, value: T)
+where
+    T: Copy + PartialEq + std::fmt::Debug + for<'py> FromPyObject<'py>,
+    for<'py> T: IntoPyObject<'py, Error = PyErr>,
+{
+    Python::with_gil(|py| {
+        b.iter(|| {
+            let obj = black_box(value)
+                .into_pyobject(py)
+                .unwrap()
+                .into_any()
+                .into_bound();
+            let roundtripped: T = black_box(&obj).extract().unwrap();
+            debug_assert_eq!(roundtripped, value);
+        });
+    });
+}
+
+fn duration_roundtrip(b: &mut Bencher<'_>) {
+    bench_roundtrip(b, Duration::days(3) + Duration::microseconds(-1_234_567));
+}
+
+fn date_roundtrip(b: &mut Bencher<'_>) {
+    bench_roundtrip(b, date());
+}
+
+fn time_roundtrip(b: &mut Bencher<'_>) {
+    bench_roundtrip(b, time());
+}
+
+fn primitive_datetime_roundtrip(b: &mut Bencher<'_>) {
+    bench_roundtrip(b, PrimitiveDateTime::new(date(), time()));
+}
+
+fn offset_datetime_roundtrip(b: &mut Bencher<'_>) {
+    let offset = UtcOffset::from_hms(5, 30, 0).unwrap();
+    bench_roundtrip::<OffsetDateTime>(
+        b,
+        PrimitiveDateTime::new(date(), time()).assume_offset(offset),
+    );
+}
+
+fn offset_datetime_utc_dataset_into_pyobject(b: &mut Bencher<'_>) {
+    // Every element shares the same offset, which is the common case the timezone cache targets
+    let start = PrimitiveDateTime::new(date(), time()).assume_utc();
+    let data: Vec<OffsetDateTime> = (0..1_000_000)
+        .map(|i| start + Duration::seconds(i))
+        .collect();
+    Python::with_gil(|py| {
+        b.iter(|| {
+            for dt in black_box(&data) {
+                black_box(dt.into_pyobject(py).unwrap());
+            }
+        });
+    });
+}
+
+fn date_dataset_extract(b: &mut Bencher<'_>) {
+    let start = date();
+    Python::with_gil(|py| {
+        let data: Vec<Bound<'_, PyAny>> = (0..1_000_000)
+            .map(|i| {
+                (start + Duration::days(i % 1_000))
+                    .into_pyobject(py)
+                    .unwrap()
+                    .into_any()
+            })
+            .collect();
+        b.iter(|| {
+            for obj in black_box(&data) {
+                black_box(obj.extract::<Date>().unwrap());
+            }
+        });
+    });
+}
+
+/// A tz-naive `pandas.DatetimeIndex` of one million seconds, or `None` without pandas.
+fn pandas_index(py: Python<'_>) -> Option<Bound<'_, PyAny>> {
+    let pandas = py.import("pandas").ok()?;
+    let kwargs = [("periods", 1_000_000)].into_py_dict(py).unwrap();
+    kwargs.set_item("freq", "s").unwrap();
+    Some(
+        pandas
+            .getattr("date_range")
+            .unwrap()
+            .call(("2024-02-29",), Some(&kwargs))
+            .unwrap(),
+    )
+}
+
+fn primitive_datetime_index_extract(b: &mut Bencher<'_>) {
+    Python::with_gil(|py| {
+        let index = pandas_index(py).unwrap();
+        b.iter(|| black_box(extract_primitive_datetimes_from_index(black_box(&index)).unwrap()));
+    });
+}
+
+fn primitive_datetime_index_extract_loop(b: &mut Bencher<'_>) {
+    Python::with_gil(|py| {
+        let index = pandas_index(py).unwrap();
+        b.iter(|| {
+            let datetimes: Vec<PrimitiveDateTime> = black_box(&index)
+                .try_iter()
+                .unwrap()
+                .map(|item| item.unwrap().extract().unwrap())
+                .collect();
+            black_box(datetimes)
+        });
+    });
+}
+
+fn durations() -> Vec<Duration> {
+    (0..1_000_000)
+        .map(|i| Duration::new(i * 37, (i * 1_234) as i32))
+        .collect()
+}
+
+fn durations_to_pandas_timedelta_index(b: &mut Bencher<'_>) {
+    let data = durations();
+    Python::with_gil(|py| {
+        b.iter(|| black_box(durations_to_timedelta_index(py, black_box(&data)).unwrap()));
+    });
+}
+
+fn durations_to_pandas_timedelta_index_list(b: &mut Bencher<'_>) {
+    let data = durations();
+    Python::with_gil(|py| {
+        let timedelta_index = py
+            .import("pandas")
+            .unwrap()
+            .getattr("TimedeltaIndex")
+            .unwrap();
+        b.iter(|| {
+            let list = PyList::new(py, black_box(&data)).unwrap();
+            black_box(timedelta_index.call1((list,)).unwrap())
+        });
+    });
+}
+
+fn criterion_benchmark(c: &mut Criterion) {
+    eprintln!("benchmarking `time` conversions using the {} API", API);
+    c.bench_function("time_duration_roundtrip", duration_roundtrip);
+    c.bench_function("time_date_roundtrip", date_roundtrip);
+    c.bench_function("time_time_roundtrip", time_roundtrip);
+    c.bench_function(
+        "time_primitive_datetime_roundtrip",
+        primitive_datetime_roundtrip,
+    );
+    c.bench_function("time_offset_datetime_roundtrip", offset_datetime_roundtrip);
+
+    let mut group = c.benchmark_group("time_dataset");
+    group.sample_size(10);
+    group.bench_function(
+        "offset_datetime_utc_1m_into_pyobject",
+        offset_datetime_utc_dataset_into_pyobject,
+    );
+    group.bench_function("date_1m_extract", date_dataset_extract);
+    if Python::with_gil(|py| pandas_index(py).is_some()) {
+        group.bench_function(
+            "primitive_datetime_1m_index_extract",
+            primitive_datetime_index_extract,
+        );
+        group.bench_function(
+            "primitive_datetime_1m_index_extract_loop",
+            primitive_datetime_index_extract_loop,
+        );
+        group.bench_function(
+            "duration_1m_to_timedelta_index",
+            durations_to_pandas_timedelta_index,
+        );
+        group.bench_function(
+            "duration_1m_to_timedelta_index_list",
+            durations_to_pandas_timedelta_index_list,
+        );
+    } else {
+        eprintln!(
+            "pandas is not installed, skipping the DatetimeIndex and TimedeltaIndex benchmarks"
+        );
+    }
+    group.finish();
+}
+
+criterion_group!(benches, criterion_benchmark);
+criterion_main!(benches);
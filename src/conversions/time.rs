@@ -42,34 +42,48 @@
 // //! ```
 
 use crate::conversion::IntoPyObject;
-use crate::exceptions::{PyTypeError, PyUserWarning, PyValueError};
-#[cfg(Py_LIMITED_API)]
+use crate::exceptions::{
+    PyImportError, PyKeyError, PyOverflowError, PyTypeError, PyUserWarning, PyValueError,
+    PyZeroDivisionError,
+};
 use crate::sync::GILOnceCell;
+use crate::type_object::PyTypeCheck;
 use crate::types::any::PyAnyMethods;
+use crate::types::bytes::PyBytesMethods;
 #[cfg(not(Py_LIMITED_API))]
-use crate::types::datetime::timezone_from_offset;
+use crate::types::datetime::{timezone_from_offset, timezone_utc};
+use crate::types::dict::PyDictMethods;
+use crate::types::string::PyStringMethods;
+use crate::types::typeobject::PyTypeMethods;
+use crate::types::{
+    IntoPyDict, PyBool, PyBytes, PyDict, PyInt, PyList, PyNone, PyString, PyTuple, PyType,
+};
 #[cfg(not(Py_LIMITED_API))]
 use crate::types::{
     PyDate, PyDateAccess, PyDateTime, PyDelta, PyDeltaAccess, PyTime, PyTimeAccess, PyTzInfo,
     PyTzInfoAccess,
 };
-use crate::types::{PyInt, PyNone};
-use crate::{ffi, Bound, FromPyObject, PyAny, PyErr, PyObject, PyResult, Python};
 #[cfg(Py_LIMITED_API)]
-use crate::{intern, DowncastError};
+use crate::DowncastError;
+use crate::{ffi, intern, Bound, FromPyObject, Py, PyAny, PyErr, PyObject, PyResult, Python};
 #[allow(deprecated)]
 use crate::{IntoPy, ToPyObject};
+use std::cell::Cell;
+use std::fmt::Write;
+use std::sync::atomic::{AtomicBool, Ordering as AtomicOrdering};
+use std::sync::{Mutex, PoisonError};
 
 // use chrono::{
 //     offset::{FixedOffset, Utc},
 //     DateTime, Datelike, Duration, NaiveDate, NaiveDateTime, NaiveTime, Offset, TimeZone, Timelike,
 // };
 
+use time::format_description::BorrowedFormatItem;
 use time::OffsetDateTime;
 use time::PrimitiveDateTime;
 use time::Time;
 use time::{Date, UtcOffset};
-use time::{Duration, Month};
+use time::{Duration, Month, Weekday};
 
 #[allow(deprecated)]
 impl ToPyObject for Duration {
@@ -87,6 +101,12 @@ impl IntoPy<PyObject> for Duration {
     }
 }
 
+/// Converts to a Python `datetime.timedelta`.
+///
+/// `timedelta` only has microsecond resolution, so any sub-microsecond part of the duration is
+/// truncated towards zero, e.g. both `Duration::nanoseconds(1500)` and `Duration::nanoseconds(1999)`
+/// become `timedelta(microseconds=1)`, and `Duration::nanoseconds(-1500)` becomes
+/// `timedelta(microseconds=-1)`.
 impl<'py> IntoPyObject<'py> for Duration {
     #[cfg(Py_LIMITED_API)]
     type Target = PyAny;
@@ -96,38 +116,7 @@ impl<'py> IntoPyObject<'py> for Duration {
     type Error = PyErr;
 
     fn into_pyobject(self, py: Python<'py>) -> Result<Self::Output, Self::Error> {
-        // Total number of days
-        let days = self.whole_days();
-        // Remainder of seconds
-        let secs_dur = self - Duration::days(days);
-        let secs = secs_dur.whole_seconds();
-        // Fractional part of the microseconds
-        let micros = (secs_dur - Duration::seconds(secs_dur.whole_seconds())).whole_microseconds();
-        // This should never panic since we are just getting the fractional
-        // part of the total microseconds, which should never overflow.
-        // .unwrap();
-
-        #[cfg(not(Py_LIMITED_API))]
-        {
-            // We do not need to check the days i64 to i32 cast from rust because
-            // python will panic with OverflowError.
-            // We pass true as the `normalize` parameter since we'd need to do several checks here to
-            // avoid that, and it shouldn't have a big performance impact.
-            // The seconds and microseconds cast should never overflow since it's at most the number of seconds per day
-            PyDelta::new(
-                py,
-                days.try_into().unwrap_or(i32::MAX),
-                secs.try_into()?,
-                micros.try_into()?,
-                true,
-            )
-        }
-
-        #[cfg(Py_LIMITED_API)]
-        {
-            DatetimeTypes::try_get(py)
-                .and_then(|dt| dt.timedelta.bind(py).call1((days, secs, micros)))
-        }
+        delta_args_to_py_delta(py, (&self).into())
     }
 }
 
@@ -147,10 +136,8 @@ impl<'py> IntoPyObject<'py> for &Duration {
 
 impl FromPyObject<'_> for Month {
     fn extract_bound(ob: &Bound<'_, PyAny>) -> PyResult<Self> {
-        ob.extract::<u8>()? // 1-based month
-            .saturating_sub(1)
-            .try_into()
-            .or_else(|_| Err(PyValueError::new_err("invalid month")))
+        // `Month` is 1-based, as are Python's months
+        Month::try_from(ob.extract::<u8>()?).map_err(|_| PyValueError::new_err("invalid month"))
     }
 }
 
@@ -172,12 +159,302 @@ impl<'py> IntoPyObject<'py> for Month {
     }
 }
 
+/// Extracts a [`Month`] from one of Python's `calendar.JANUARY` to `calendar.DECEMBER` constants,
+/// i.e. an `int` from 1 to 12.
+pub fn month_from_calendar_const(ob: &Bound<'_, PyAny>) -> PyResult<Month> {
+    ob.extract()
+}
+
+/// Converts a [`Month`] to the matching `calendar.JANUARY` to `calendar.DECEMBER` constant, i.e.
+/// an `int` from 1 to 12. This is the inverse of [`month_from_calendar_const`].
+pub fn month_to_calendar_const(py: Python<'_>, month: Month) -> PyResult<Bound<'_, PyInt>> {
+    month.into_pyobject(py)
+}
+
+/// Returns the abbreviated name of `month` from Python's `calendar.month_abbr`, e.g. `"Jan"`.
+///
+/// As in Python, the name depends on the current locale.
+pub fn month_into_py_abbr(py: Python<'_>, month: Month) -> PyResult<Bound<'_, PyString>> {
+    py.import(intern!(py, "calendar"))?
+        .getattr(intern!(py, "month_abbr"))?
+        .get_item(month as u8)?
+        .downcast_into()
+        .map_err(Into::into)
+}
+
+/// Converts a [`Duration`] to a Python `int` of its total number of microseconds, as commonly
+/// used to store durations in SQLite.
+///
+/// Any sub-microsecond part of the duration is truncated towards zero. An `OverflowError` is
+/// raised if the total does not fit in an `i64`, i.e. for durations longer than roughly
+/// 292,000 years in either direction.
+pub fn duration_to_microseconds_i64(
+    py: Python<'_>,
+    duration: Duration,
+) -> PyResult<Bound<'_, PyInt>> {
+    let micros: i64 = duration
+        .whole_microseconds()
+        .try_into()
+        .map_err(|_| PyOverflowError::new_err("duration does not fit in i64 microseconds"))?;
+    Ok(micros.into_pyobject(py)?)
+}
+
+/// Converts a Python `int` of microseconds, as produced by [`duration_to_microseconds_i64`], back
+/// into a [`Duration`].
+///
+/// The value must fit in an `i64`; every such value is representable as a [`Duration`].
+pub fn duration_from_microseconds_i64(ob: &Bound<'_, PyAny>) -> PyResult<Duration> {
+    let micros: i64 = ob.extract()?;
+    Ok(Duration::microseconds(micros))
+}
+
+/// Converts a [`Duration`] to a Python `int` of its total number of nanoseconds, as used by e.g.
+/// `time.monotonic_ns()`.
+///
+/// Unlike the `timedelta` conversion this is lossless, and since Python's `int` has arbitrary
+/// precision every [`Duration`] fits.
+pub fn duration_to_py_nanos_i128(py: Python<'_>, duration: Duration) -> PyResult<Bound<'_, PyInt>> {
+    Ok(duration.whole_nanoseconds().into_pyobject(py)?)
+}
+
+/// Converts a Python `int` of nanoseconds, as produced by [`duration_to_py_nanos_i128`], back
+/// into the exact [`Duration`].
+///
+/// An `OverflowError` is raised for values beyond the range of [`Duration`].
+pub fn duration_from_py_nanos(ob: &Bound<'_, PyAny>) -> PyResult<Duration> {
+    let nanos: i128 = ob.extract()?;
+    let seconds = i64::try_from(nanos / 1_000_000_000)
+        .map_err(|_| PyOverflowError::new_err("nanoseconds out of range for Duration"))?;
+    // Both parts have the same sign, so no normalization can overflow
+    Ok(Duration::new(seconds, (nanos % 1_000_000_000) as i32))
+}
+
+/// Converts a slice of [`Duration`]s to a `pandas.TimedeltaIndex` with full nanosecond precision.
+///
+/// The durations are handed to pandas as a single buffer of `int64` nanoseconds, pandas' default
+/// unit, instead of creating a `timedelta` per element, which would also drop the
+/// nanoseconds. An `ImportError` is raised if pandas is not installed, and an `OverflowError` for
+/// durations outside of pandas' range of about ±292 years.
+pub fn durations_to_timedelta_index<'py>(
+    py: Python<'py>,
+    durations: &[Duration],
+) -> PyResult<Bound<'py, PyAny>> {
+    static TIMEDELTA_INDEX: InterpreterOnceCell<PyObject> = InterpreterOnceCell::new();
+    let timedelta_index = TIMEDELTA_INDEX
+        .get_or_try_init(py, || {
+            py.import(intern!(py, "pandas"))?
+                .getattr(intern!(py, "TimedeltaIndex"))
+                .map(Bound::unbind)
+        })?
+        .bind(py);
+    durations_to_timedelta_index_with(timedelta_index, durations)
+}
+
+/// Implements [`durations_to_timedelta_index`] given the `TimedeltaIndex` class.
+fn durations_to_timedelta_index_with<'py>(
+    timedelta_index: &Bound<'py, PyAny>,
+    durations: &[Duration],
+) -> PyResult<Bound<'py, PyAny>> {
+    let py = timedelta_index.py();
+    let mut bytes = Vec::with_capacity(durations.len() * 8);
+    for (i, duration) in durations.iter().enumerate() {
+        let nanos = i64::try_from(duration.whole_nanoseconds())
+            .ok()
+            // pandas reserves the smallest `int64` for `NaT`
+            .filter(|&nanos| nanos != i64::MIN)
+            .ok_or_else(|| {
+                PyOverflowError::new_err(format!(
+                    "{} at position {} is out of range for a pandas TimedeltaIndex",
+                    duration, i
+                ))
+            })?;
+        bytes.extend_from_slice(&nanos.to_ne_bytes());
+    }
+    let nanos = py
+        .import(intern!(py, "array"))?
+        .getattr(intern!(py, "array"))?
+        .call1((intern!(py, "q"), PyBytes::new(py, &bytes)))?;
+    timedelta_index.call1((nanos,))
+}
+
+/// How [`duration_into_pydelta_rounded`] handles the part of a [`Duration`] below Python's
+/// microsecond resolution. The names follow Python's `decimal` rounding modes.
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Hash, Default)]
+pub enum SubMicroMode {
+    /// Truncate towards zero, like the regular conversion.
+    #[default]
+    Truncate,
+    /// Round to the nearest microsecond, with ties to the even microsecond.
+    HalfEven,
+    /// Round to the nearest microsecond, with ties away from zero.
+    HalfUp,
+    /// Round to the nearest microsecond, with ties towards zero.
+    HalfDown,
+}
+
+impl SubMicroMode {
+    fn round_nanos_to_micros(self, nanos: i128) -> i128 {
+        let (micros, remainder) = (nanos / 1000, (nanos % 1000).abs());
+        let away_from_zero = match self {
+            SubMicroMode::Truncate => false,
+            _ if remainder != 500 => remainder > 500,
+            SubMicroMode::HalfEven => micros % 2 != 0,
+            SubMicroMode::HalfUp => true,
+            SubMicroMode::HalfDown => false,
+        };
+        if away_from_zero {
+            micros + nanos.signum()
+        } else {
+            micros
+        }
+    }
+}
+
+/// Converts a [`Duration`] to a Python `datetime.timedelta`, rounding its sub-microsecond part
+/// according to `mode` instead of truncating it.
+///
+/// Rounding up may carry into the seconds and days, e.g. `999_999_500` nanoseconds rounded with
+/// [`SubMicroMode::HalfUp`] become `timedelta(seconds=1)`.
+pub fn duration_into_pydelta_rounded(
+    py: Python<'_>,
+    duration: Duration,
+    mode: SubMicroMode,
+) -> PyResult<<Duration as IntoPyObject<'_>>::Output> {
+    let micros = mode.round_nanos_to_micros(duration.whole_nanoseconds());
+    delta_args_to_py_delta(py, DeltaArgs::from_micros(micros))
+}
+
+/// Converts a [`Duration`] to a Python `datetime.timedelta` after clamping it to `min..=max`,
+/// also returning whether it was clamped.
+///
+/// This enforces limits such as a timeout capped at 24 hours while letting the caller know that
+/// the value was adjusted. A `ValueError` is raised if `min` is greater than `max`.
+pub fn duration_into_pydelta_clamped(
+    py: Python<'_>,
+    duration: Duration,
+    min: Duration,
+    max: Duration,
+) -> PyResult<(<Duration as IntoPyObject<'_>>::Output, bool)> {
+    if min > max {
+        return Err(PyValueError::new_err(format!(
+            "min {} is greater than max {}",
+            min, max
+        )));
+    }
+    let clamped = duration.clamp(min, max);
+    Ok((clamped.into_pyobject(py)?, clamped != duration))
+}
+
+/// Converts a [`Duration`] to a Python `datetime.timedelta`, calling `timedelta(weeks=n)` when
+/// the duration is a whole number of weeks.
+///
+/// The result is equal to the one of the regular conversion; only the way it is constructed
+/// differs.
+pub fn duration_into_pydelta_weeks(
+    py: Python<'_>,
+    duration: Duration,
+) -> PyResult<<Duration as IntoPyObject<'_>>::Output> {
+    let weeks = duration.whole_weeks();
+    if Duration::weeks(weeks) != duration {
+        return duration.into_pyobject(py);
+    }
+    let kwargs = [(intern!(py, "weeks"), weeks)].into_py_dict(py)?;
+    #[cfg(not(Py_LIMITED_API))]
+    let delta = py
+        .get_type::<PyDelta>()
+        .call((), Some(&kwargs))?
+        .downcast_into()?;
+    #[cfg(Py_LIMITED_API)]
+    let delta = DatetimeTypes::try_get(py)?
+        .timedelta
+        .bind(py)
+        .call((), Some(&kwargs))?;
+    Ok(delta)
+}
+
+/// Formats a [`Duration`] exactly like Python's `str()` of the converted `timedelta`, e.g.
+/// `"1 day, 2:03:04.000005"` or `"-1 day, 23:59:59"` for minus one second.
+///
+/// Sub-microsecond precision is truncated, and an `OverflowError` is raised for durations which
+/// don't fit in a `timedelta`, as for the conversion itself.
+pub fn duration_to_pystr(py: Python<'_>, duration: Duration) -> PyResult<Bound<'_, PyString>> {
+    let DeltaArgs { days, secs, micros } = (&duration).into();
+    if days.abs() > 999_999_999 {
+        return Err(PyOverflowError::new_err(format!(
+            "days={}; must have magnitude <= 999999999",
+            days
+        )));
+    }
+    let mut s = String::new();
+    if days != 0 {
+        let plural = if days.abs() == 1 { "" } else { "s" };
+        let _ = write!(s, "{} day{}, ", days, plural);
+    }
+    let _ = write!(s, "{}:{:02}:{:02}", secs / 3600, secs / 60 % 60, secs % 60);
+    if micros != 0 {
+        let _ = write!(s, ".{:06}", micros);
+    }
+    Ok(PyString::new(py, &s))
+}
+
+/// Returns Python's `datetime.datetime.resolution`, the `timedelta(microseconds=1)` below which
+/// the conversions truncate.
+pub fn py_datetime_resolution(py: Python<'_>) -> PyResult<<Duration as IntoPyObject<'_>>::Output> {
+    static RESOLUTION: InterpreterOnceCell<PyObject> = InterpreterOnceCell::new();
+    let resolution = RESOLUTION
+        .get_or_try_init(py, || {
+            #[cfg(not(Py_LIMITED_API))]
+            let datetime = py.get_type::<PyDateTime>().into_any();
+            #[cfg(Py_LIMITED_API)]
+            let datetime = DatetimeTypes::try_get(py)?.datetime.bind(py).clone();
+            datetime
+                .getattr(intern!(py, "resolution"))
+                .map(Bound::unbind)
+        })?
+        .bind(py)
+        .clone();
+    #[cfg(not(Py_LIMITED_API))]
+    let resolution = resolution.downcast_into()?;
+    Ok(resolution)
+}
+
+/// Computes `divmod(duration, divisor)` of the converted `timedelta`s, returning a Python tuple of
+/// the `int` quotient and the `timedelta` remainder.
+///
+/// Like Python, this works at microsecond resolution (sub-microsecond parts are truncated first)
+/// and rounds the quotient towards negative infinity, so the remainder has the sign of `divisor`.
+/// A zero `divisor` raises a `ZeroDivisionError`.
+pub fn duration_divmod_into_py(
+    py: Python<'_>,
+    duration: Duration,
+    divisor: Duration,
+) -> PyResult<Bound<'_, PyTuple>> {
+    let (micros, _) = truncate_nanos_to_micros(duration.whole_nanoseconds());
+    let (divisor_micros, _) = truncate_nanos_to_micros(divisor.whole_nanoseconds());
+    if divisor_micros == 0 {
+        return Err(PyZeroDivisionError::new_err(
+            "integer division or modulo by zero",
+        ));
+    }
+    let mut quotient = micros / divisor_micros;
+    let mut remainder = micros % divisor_micros;
+    if remainder != 0 && (remainder < 0) != (divisor_micros < 0) {
+        quotient -= 1;
+        remainder += divisor_micros;
+    }
+    // The remainder is smaller than the divisor, so these casts are safe
+    let remainder = Duration::seconds((remainder / 1_000_000) as i64)
+        + Duration::microseconds((remainder % 1_000_000) as i64);
+    (quotient, remainder).into_pyobject(py)
+}
+
 impl FromPyObject<'_> for Duration {
     fn extract_bound(ob: &Bound<'_, PyAny>) -> PyResult<Duration> {
-        // Python size are much lower than rust size so we do not need bound checks.
+        // Python size are much lower than rust size so these always fit in a `Duration`:
         // 0 <= microseconds < 1000000
         // 0 <= seconds < 3600*24
         // -999999999 <= days <= 999999999
+        // The sum is still checked in case a subclass reports out of range fields.
         #[cfg(not(Py_LIMITED_API))]
         let (days, seconds, microseconds) = {
             let delta = ob.downcast::<PyDelta>()?;
@@ -196,12 +473,39 @@ impl FromPyObject<'_> for Duration {
                 ob.getattr(intern!(ob.py(), "microseconds"))?.extract()?,
             )
         };
-        Ok(
-            Duration::days(days)
-                + Duration::seconds(seconds)
-                + Duration::microseconds(microseconds),
-        )
+        duration_from_parts(days, seconds, microseconds)
+    }
+}
+
+/// Extracts a [`Duration`] from a Python `datetime.timedelta`, guaranteeing that converting it
+/// back gives a `timedelta` equal to `ob`, with the same `days`, `seconds` and `microseconds`.
+///
+/// Every `timedelta` is a whole number of microseconds well within the range of [`Duration`], so
+/// unlike the other direction this is lossless; the components are still compared, so that a
+/// `timedelta` subclass reporting inconsistent fields raises a `ValueError` rather than silently
+/// changing.
+pub fn duration_extract_exact(ob: &Bound<'_, PyAny>) -> PyResult<Duration> {
+    let py = ob.py();
+    let duration: Duration = ob.extract()?;
+    let DeltaArgs { days, secs, micros } = (&duration).into();
+    let exact = ob.getattr(intern!(py, "days"))?.extract::<i128>()? == days
+        && ob.getattr(intern!(py, "seconds"))?.extract::<i32>()? == secs
+        && ob.getattr(intern!(py, "microseconds"))?.extract::<i32>()? == micros;
+    if !exact {
+        return Err(PyValueError::new_err(format!(
+            "{} cannot be represented exactly as a Duration",
+            ob.repr()?
+        )));
     }
+    Ok(duration)
+}
+
+fn duration_from_parts(days: i64, seconds: i64, microseconds: i64) -> PyResult<Duration> {
+    days.checked_mul(86_400)
+        .map(Duration::seconds)
+        .and_then(|duration| duration.checked_add(Duration::seconds(seconds)))
+        .and_then(|duration| duration.checked_add(Duration::microseconds(microseconds)))
+        .ok_or_else(|| PyOverflowError::new_err("timedelta is out of range for Duration"))
 }
 
 #[allow(deprecated)]
@@ -220,6 +524,10 @@ impl IntoPy<PyObject> for Date {
     }
 }
 
+/// Converts to a Python `datetime.date`.
+///
+/// Both `time` and Python use the proleptic Gregorian calendar, without any switch to the Julian
+/// calendar, so each date maps to the same day in both, even before the 1582 calendar reform.
 impl<'py> IntoPyObject<'py> for Date {
     #[cfg(Py_LIMITED_API)]
     type Target = PyAny;
@@ -229,16 +537,8 @@ impl<'py> IntoPyObject<'py> for Date {
     type Error = PyErr;
 
     fn into_pyobject(self, py: Python<'py>) -> Result<Self::Output, Self::Error> {
-        let DateArgs { year, month, day } = (&self).into();
-        #[cfg(not(Py_LIMITED_API))]
-        {
-            PyDate::new(py, year, month, day)
-        }
-
-        #[cfg(Py_LIMITED_API)]
-        {
-            DatetimeTypes::try_get(py).and_then(|dt| dt.date.bind(py).call1((year, month, day)))
-        }
+        check_py_year(self.year())?;
+        date_into_py_unchecked(py, self)
     }
 }
 
@@ -256,6 +556,290 @@ impl<'py> IntoPyObject<'py> for &Date {
     }
 }
 
+/// Converts a [`Date`] to a Python `datetime.date` without checking that its year is within
+/// Python's supported range of `1..=9999`.
+///
+/// This is intended for bulk conversion of dates which are already known to be valid. Passing a
+/// date outside of that range is caught by a debug assertion; in release builds Python itself
+/// will still raise an error when constructing the `date`.
+pub fn date_into_py_unchecked(
+    py: Python<'_>,
+    date: Date,
+) -> PyResult<<Date as IntoPyObject<'_>>::Output> {
+    debug_assert!(
+        check_py_year(date.year()).is_ok(),
+        "date {} is out of range for Python",
+        date
+    );
+    let DateArgs { year, month, day } = (&date).into();
+    if let Some(date) = call_registered_class(py, DatetimeClass::Date, (year, month, day))? {
+        return Ok(date);
+    }
+    #[cfg(not(Py_LIMITED_API))]
+    {
+        PyDate::new(py, year, month, day)
+    }
+
+    #[cfg(Py_LIMITED_API)]
+    {
+        DatetimeTypes::try_get(py).and_then(|dt| dt.date.bind(py).call1((year, month, day)))
+    }
+}
+
+/// Extracts the date part of a `pandas.Timestamp`, e.g. an element of a `DatetimeIndex`, through
+/// its public `year`, `month` and `day` accessors.
+///
+/// Any other object is extracted with the regular [`Date`] conversion.
+pub fn date_from_pandas_timestamp(ob: &Bound<'_, PyAny>) -> PyResult<Date> {
+    if is_pandas_class(ob, "Timestamp")? {
+        date_from_accessors(ob)
+    } else {
+        ob.extract()
+    }
+}
+
+/// Whether `ob` is an instance of the pandas class `name`, e.g. `pandas.Timestamp`, checked by name
+/// so that pandas needn't be imported.
+fn is_pandas_class(ob: &Bound<'_, PyAny>, name: &str) -> PyResult<bool> {
+    let py = ob.py();
+    for cls in ob.get_type().getattr(intern!(py, "__mro__"))?.try_iter()? {
+        let cls = cls?;
+        let module = cls.getattr(intern!(py, "__module__"))?.to_string();
+        if (module == "pandas" || module.starts_with("pandas."))
+            && cls.getattr(intern!(py, "__name__"))?.to_string() == name
+        {
+            return Ok(true);
+        }
+    }
+    Ok(false)
+}
+
+/// Returns the weekday of the first day of `month` in `year` as a Python `int`, like Python's
+/// `date(year, month, 1).weekday()`, i.e. with Monday as 0 and Sunday as 6.
+///
+/// Useful for laying out calendar grids. The year must be within Python's supported range.
+pub fn first_weekday_of_month_into_py(
+    py: Python<'_>,
+    year: i32,
+    month: Month,
+) -> PyResult<Bound<'_, PyInt>> {
+    check_py_year(year)?;
+    let first = Date::from_calendar_date(year, month, 1)
+        .map_err(|_| PyValueError::new_err("invalid or out-of-range date"))?;
+    Ok(first
+        .weekday()
+        .number_days_from_monday()
+        .into_pyobject(py)?)
+}
+
+/// Returns whether the Python `date` or `datetime` `dt` is in a leap year of the proleptic
+/// Gregorian calendar, e.g. `false` for 1900 and `true` for 2000.
+pub fn datetime_is_leap_year(dt: &Bound<'_, PyAny>) -> PyResult<bool> {
+    let date: Date = dt.extract()?;
+    Ok(time::util::is_leap_year(date.year()))
+}
+
+/// Returns whether `date` falls on a Saturday or Sunday, as a Python `bool`.
+pub fn date_is_weekend_into_py(py: Python<'_>, date: Date) -> PyResult<Bound<'_, PyBool>> {
+    check_py_year(date.year())?;
+    let is_weekend = matches!(date.weekday(), Weekday::Saturday | Weekday::Sunday);
+    Ok(PyBool::new(py, is_weekend).to_owned())
+}
+
+/// Moves `date` by `n` business days, skipping Saturdays and Sundays, and converts the result to
+/// a Python `datetime.date`. A negative `n` moves backwards.
+///
+/// Starting on a weekend, the first step lands on the next (or, for a negative `n`, previous)
+/// business day. For `n == 0` the date is returned unchanged, even on a weekend. An
+/// `OverflowError` is raised if the result is out of range.
+pub fn date_add_business_days(
+    py: Python<'_>,
+    date: Date,
+    n: i64,
+) -> PyResult<<Date as IntoPyObject<'_>>::Output> {
+    // Julian day 0 is a Monday, so this is the number of days from Monday
+    let weekday = |julian_day: i128| julian_day.rem_euclid(7);
+    let mut day = i128::from(date.to_julian_day());
+    // A weekend counts as the preceding Friday when moving forwards, and as the following
+    // Monday when moving backwards, so that skipping whole weeks below stays correct
+    match weekday(day) {
+        wd @ 5..=6 if n > 0 => day -= wd - 4,
+        wd @ 5..=6 if n < 0 => day += 7 - wd,
+        _ => {}
+    }
+    day += i128::from(n / 5) * 7;
+    let mut remaining = n % 5;
+    while remaining != 0 {
+        let step = remaining.signum();
+        day += i128::from(step);
+        if weekday(day) < 5 {
+            remaining -= step;
+        }
+    }
+    let result = i32::try_from(day)
+        .ok()
+        .and_then(|day| Date::from_julian_day(day).ok())
+        .filter(|result| check_py_year(result.year()).is_ok())
+        .ok_or_else(|| PyOverflowError::new_err("date value out of range"))?;
+    result.into_pyobject(py)
+}
+
+/// Parses an ISO 8601 week date such as `"2021-W01-1"` (the Monday of the first week of 2021)
+/// into a [`Date`], like Python's `date.fromisocalendar()` does for its components.
+///
+/// The week must be in `1..=53`, and week 53 only exists in ISO years with 53 weeks. The weekday
+/// must be in `1..=7`, where 1 is Monday. A `ValueError` names the offending component.
+pub fn date_from_iso_week_string(_py: Python<'_>, s: &Bound<'_, PyString>) -> PyResult<Date> {
+    let s = s.to_cow()?;
+    let invalid = || PyValueError::new_err(format!("invalid ISO week date {:?}", s));
+    let bytes = s.as_bytes();
+    if bytes.len() != 10 || bytes[4] != b'-' || bytes[5] != b'W' || bytes[8] != b'-' {
+        return Err(invalid());
+    }
+    let number = |range: std::ops::Range<usize>| -> PyResult<u16> {
+        let digits = &s[range];
+        if !digits.bytes().all(|b| b.is_ascii_digit()) {
+            return Err(invalid());
+        }
+        digits.parse().map_err(|_| invalid())
+    };
+    let year = number(0..4)?;
+    let week = number(6..8)?;
+    let weekday = number(9..10)?;
+    check_py_year(year.into())?;
+    let weekday = u8::try_from(weekday)
+        .ok()
+        .and_then(iso_weekday)
+        .ok_or_else(|| {
+            PyValueError::new_err(format!(
+                "weekday {} is out of range, must be in 1..=7",
+                weekday
+            ))
+        })?;
+    if !(1..=53).contains(&week) {
+        return Err(PyValueError::new_err(format!(
+            "week {} is out of range, must be in 1..=53",
+            week
+        )));
+    }
+    let date = Date::from_iso_week_date(year.into(), week as u8, weekday)
+        .map_err(|_| PyValueError::new_err(format!("ISO year {} has no week {}", year, week)))?;
+    // The last days of ISO year 9999 are in calendar year 10000
+    check_py_year(date.year())?;
+    Ok(date)
+}
+
+/// Builds a [`Date`] from ISO calendar components like Python's `date.fromisocalendar()`,
+/// validating them in the same order and with the `ValueError` messages of CPython.
+///
+/// `iso_year` must be in Python's year range, `week` in `1..=53`, where week 53 only exists in
+/// ISO years with 53 weeks, and `weekday` in `1..=7`, where 1 is Monday.
+pub fn date_from_py_isocalendar(
+    _py: Python<'_>,
+    iso_year: i32,
+    week: u8,
+    weekday: u8,
+) -> PyResult<Date> {
+    if !PY_YEAR_RANGE.contains(&iso_year) {
+        return Err(PyValueError::new_err(format!(
+            "Year is out of range: {}",
+            iso_year
+        )));
+    }
+    if !(1..=53).contains(&week)
+        || Date::from_iso_week_date(iso_year, week, Weekday::Monday).is_err()
+    {
+        return Err(PyValueError::new_err(format!("Invalid week: {}", week)));
+    }
+    let weekday = iso_weekday(weekday).ok_or_else(|| {
+        PyValueError::new_err(format!("Invalid day: {} (range is [1, 7])", weekday))
+    })?;
+    // Only the last days of ISO year 9999 can fail, which are in calendar year 10000
+    Date::from_iso_week_date(iso_year, week, weekday)
+        .ok()
+        .filter(|date| PY_YEAR_RANGE.contains(&date.year()))
+        .ok_or_else(|| PyValueError::new_err(format!("year {} is out of range", iso_year + 1)))
+}
+
+/// Maps an ISO weekday number, 1 for Monday to 7 for Sunday, to a [`Weekday`].
+fn iso_weekday(number: u8) -> Option<Weekday> {
+    match number {
+        1 => Some(Weekday::Monday),
+        2 => Some(Weekday::Tuesday),
+        3 => Some(Weekday::Wednesday),
+        4 => Some(Weekday::Thursday),
+        5 => Some(Weekday::Friday),
+        6 => Some(Weekday::Saturday),
+        7 => Some(Weekday::Sunday),
+        _ => None,
+    }
+}
+
+/// Converts a [`Date`] to its 1-based day of the year as a Python `int`, like the `tm_yday` of
+/// Python's `date.timetuple()`.
+///
+/// A `ValueError` is raised for dates outside of Python's year range.
+pub fn date_to_py_yday(py: Python<'_>, date: Date) -> PyResult<Bound<'_, PyInt>> {
+    check_py_year(date.year())?;
+    Ok(date.ordinal().into_pyobject(py)?)
+}
+
+/// Builds a [`Date`] from a year and its 1-based day of the year, the inverse of
+/// [`date_to_py_yday`].
+///
+/// A `ValueError` is raised if `year` is outside of Python's year range, or if `yday` is not
+/// within the year, i.e. in `1..=365`, or `1..=366` in leap years.
+pub fn date_from_year_and_yday(_py: Python<'_>, year: i32, yday: u16) -> PyResult<Date> {
+    check_py_year(year)?;
+    let days = time::util::days_in_year(year);
+    if !(1..=days).contains(&yday) {
+        return Err(PyValueError::new_err(format!(
+            "day of year {} is out of range for year {}, must be in 1..={}",
+            yday, year, days
+        )));
+    }
+    Date::from_ordinal_date(year, yday).map_err(|_| {
+        PyValueError::new_err(format!(
+            "day of year {} is out of range for year {}",
+            yday, year
+        ))
+    })
+}
+
+/// Formats a [`Date`] as an ISO 8601 week date such as `"2021-W01-1"`, the inverse of
+/// [`date_from_iso_week_string`].
+///
+/// As for Python's `date.isocalendar()`, the year is the ISO year, which differs from the
+/// calendar year for some days around the new year.
+pub fn date_to_iso_week_string(py: Python<'_>, date: Date) -> PyResult<Bound<'_, PyString>> {
+    check_py_year(date.year())?;
+    let (year, week, weekday) = date.to_iso_week_date();
+    let iso = format!("{:04}-W{:02}-{}", year, week, weekday.number_from_monday());
+    Ok(PyString::new(py, &iso))
+}
+
+/// Converts a [`Date`] to a Python `time.struct_time`, like Python's `date.timetuple()`.
+///
+/// The time fields are zero, `tm_yday` is the 1-based day of the year and `tm_isdst` is `-1`.
+pub fn date_to_timetuple(py: Python<'_>, date: Date) -> PyResult<Bound<'_, PyAny>> {
+    check_py_year(date.year())?;
+    let DateArgs { year, month, day } = (&date).into();
+    let fields = (
+        year,
+        month,
+        day,
+        0,
+        0,
+        0,
+        date.weekday().number_days_from_monday(),
+        date.ordinal(),
+        -1,
+    );
+    py.import(intern!(py, "time"))?
+        .getattr(intern!(py, "struct_time"))?
+        .call1((fields,))
+}
+
 impl FromPyObject<'_> for Date {
     fn extract_bound(ob: &Bound<'_, PyAny>) -> PyResult<Date> {
         #[cfg(not(Py_LIMITED_API))]
@@ -296,20 +880,7 @@ impl<'py> IntoPyObject<'py> for Time {
     type Error = PyErr;
 
     fn into_pyobject(self, py: Python<'py>) -> Result<Self::Output, Self::Error> {
-        let TimeArgs {
-            hour,
-            min,
-            sec,
-            micro,
-            truncated_leap_second,
-        } = (&self).into();
-
-        #[cfg(not(Py_LIMITED_API))]
-        let time = PyTime::new(py, hour, min, sec, micro, None)?;
-
-        #[cfg(Py_LIMITED_API)]
-        let time = DatetimeTypes::try_get(py)
-            .and_then(|dt| dt.time.bind(py).call1((hour, min, sec, micro)))?;
+        let (time, truncated_leap_second) = time_to_py_time(py, &self)?;
 
         if truncated_leap_second {
             warn_truncated_leap_second(&time);
@@ -333,17 +904,81 @@ impl<'py> IntoPyObject<'py> for &Time {
     }
 }
 
+/// Converts a [`Time`] to a Python `datetime.time`, also returning whether precision was lost,
+/// i.e. whether a leap second or a sub-microsecond part had to be truncated.
+///
+/// Unlike the regular conversion, this never emits a warning for a truncated leap second.
+pub fn time_into_py_reporting(
+    py: Python<'_>,
+    time: Time,
+) -> PyResult<(<Time as IntoPyObject<'_>>::Output, bool)> {
+    let (py_time, truncated_leap_second) = time_to_py_time(py, &time)?;
+    Ok((
+        py_time,
+        truncated_leap_second || has_sub_microseconds(&time),
+    ))
+}
+
+/// The precision of the time component in ISO 8601 output, mirroring the `timespec` argument of
+/// Python's `isoformat()` methods.
+///
+/// Components beyond the requested precision are truncated, not rounded, as in Python.
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Hash, Default)]
+pub enum TimeSpec {
+    /// `Seconds` if the microsecond is zero, `Microseconds` otherwise.
+    #[default]
+    Auto,
+    /// `HH`
+    Hours,
+    /// `HH:MM`
+    Minutes,
+    /// `HH:MM:SS`
+    Seconds,
+    /// `HH:MM:SS.sss`
+    Milliseconds,
+    /// `HH:MM:SS.ffffff`
+    Microseconds,
+}
+
+/// Formats a [`Time`] like Python's `time.isoformat(timespec=...)` of the converted time.
+pub fn time_to_isoformat(
+    py: Python<'_>,
+    time: Time,
+    timespec: TimeSpec,
+) -> PyResult<Bound<'_, PyString>> {
+    let mut iso = String::new();
+    write_iso_time(&mut iso, &time, timespec);
+    Ok(PyString::new(py, &iso))
+}
+
+/// Extracts from a Python `datetime.time`, or alternatively from a `str` in the ISO 8601 format
+/// `HH:MM:SS` with an optional fractional second, e.g. `"13:45:30.500000"`.
+///
+/// Python's `datetime.time` cannot represent a leap second (`second` is at most 59), so unlike
+/// the conversion to Python, which truncates a leap second, extraction never produces one.
 impl FromPyObject<'_> for Time {
     fn extract_bound(ob: &Bound<'_, PyAny>) -> PyResult<Time> {
         #[cfg(not(Py_LIMITED_API))]
-        {
-            let time = ob.downcast::<PyTime>()?;
-            py_time_to_naive_time(time)
-        }
+        let result = ob
+            .downcast::<PyTime>()
+            .map_err(PyErr::from)
+            .and_then(py_time_to_naive_time);
         #[cfg(Py_LIMITED_API)]
-        {
-            check_type(ob, &DatetimeTypes::get(ob.py()).time, "PyTime")?;
-            py_time_to_naive_time(ob)
+        let result = check_type(ob, &DatetimeTypes::get(ob.py()).time, "PyTime")
+            .and_then(|_| py_time_to_naive_time(ob));
+        match result {
+            Ok(time) => with_extra_nanoseconds(ob, time),
+            Err(_) if ob.is_instance_of::<PyString>() => {
+                let s = ob.downcast::<PyString>()?.to_cow()?;
+                Time::parse(
+                    &s,
+                    time::macros::format_description!(
+                        "[hour]:[minute]:[second][optional [.[subsecond]]]"
+                    ),
+                )
+                .map_err(|e| PyValueError::new_err(format!("invalid time {:?}: {}", s, e)))
+            }
+            result => result,
         }
     }
 }
@@ -382,15 +1017,27 @@ impl<'py> IntoPyObject<'py> for PrimitiveDateTime {
             truncated_leap_second,
         } = (&self.time()).into();
 
+        let registered = call_registered_class(
+            py,
+            DatetimeClass::DateTime,
+            (year, month, day, hour, min, sec, micro),
+        )?;
+
         #[cfg(not(Py_LIMITED_API))]
-        let datetime = PyDateTime::new(py, year, month, day, hour, min, sec, micro, None)?;
+        let datetime = match registered {
+            Some(datetime) => datetime,
+            None => PyDateTime::new(py, year, month, day, hour, min, sec, micro, None)?,
+        };
 
         #[cfg(Py_LIMITED_API)]
-        let datetime = DatetimeTypes::try_get(py).and_then(|dt| {
-            dt.datetime
-                .bind(py)
-                .call1((year, month, day, hour, min, sec, micro))
-        })?;
+        let datetime = match registered {
+            Some(datetime) => datetime,
+            None => DatetimeTypes::try_get(py).and_then(|dt| {
+                dt.datetime
+                    .bind(py)
+                    .call1((year, month, day, hour, min, sec, micro))
+            })?,
+        };
 
         if truncated_leap_second {
             warn_truncated_leap_second(&datetime);
@@ -414,27 +1061,323 @@ impl<'py> IntoPyObject<'py> for &PrimitiveDateTime {
     }
 }
 
+/// Adds `duration` to `datetime` in Rust and converts the sum to a Python `datetime.datetime`.
+///
+/// As in Python, an `OverflowError` is raised if the sum is out of range, i.e. not within
+/// Python's years `1..=9999`, even though [`PrimitiveDateTime`] itself may be able to hold it.
+pub fn primitive_datetime_add_into_py(
+    py: Python<'_>,
+    datetime: PrimitiveDateTime,
+    duration: Duration,
+) -> PyResult<<PrimitiveDateTime as IntoPyObject<'_>>::Output> {
+    let sum = datetime
+        .checked_add(duration)
+        .filter(|sum| PY_YEAR_RANGE.contains(&sum.year()))
+        .ok_or_else(|| {
+            PyOverflowError::new_err(format!(
+                "date value out of range: {} + {} is outside of Python's years {}..={}",
+                datetime,
+                duration,
+                PY_YEAR_RANGE.start(),
+                PY_YEAR_RANGE.end()
+            ))
+        })?;
+    sum.into_pyobject(py)
+}
+
+/// Extracts from a naive Python `datetime.datetime`. As for [`Time`], the result never holds a
+/// leap second.
 impl FromPyObject<'_> for PrimitiveDateTime {
     fn extract_bound(dt: &Bound<'_, PyAny>) -> PyResult<PrimitiveDateTime> {
-        #[cfg(not(Py_LIMITED_API))]
-        let dt = dt.downcast::<PyDateTime>()?;
-        #[cfg(Py_LIMITED_API)]
-        check_type(dt, &DatetimeTypes::get(dt.py()).datetime, "PyDateTime")?;
+        extract_primitive_datetime(dt, FoldPolicy::Ignore)
+    }
+}
+
+/// How [`extract_primitive_datetime`] treats a naive `datetime` with `fold=1`.
+///
+/// A [`PrimitiveDateTime`] carries no offset, so the `fold` attribute is always discarded. A
+/// non-zero fold on the input may indicate that the caller expected aware semantics.
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Hash, Default)]
+pub enum FoldPolicy {
+    /// Silently discard the fold, as the [`FromPyObject`] implementation does.
+    #[default]
+    Ignore,
+    /// Emit a `UserWarning` when the fold is discarded.
+    Warn,
+    /// Raise a `ValueError` instead of discarding the fold.
+    Error,
+}
+
+/// Extracts a [`PrimitiveDateTime`] from a naive `datetime.datetime`, handling a non-zero `fold`
+/// according to `policy`.
+pub fn extract_primitive_datetime(
+    dt: &Bound<'_, PyAny>,
+    policy: FoldPolicy,
+) -> PyResult<PrimitiveDateTime> {
+    #[cfg(not(Py_LIMITED_API))]
+    let dt = dt.downcast::<PyDateTime>()?;
+    #[cfg(Py_LIMITED_API)]
+    check_type(dt, &DatetimeTypes::get(dt.py()).datetime, "PyDateTime")?;
+
+    // If the user tries to convert a timezone aware datetime into a naive one,
+    // we return a hard error. We could silently remove tzinfo, or assume local timezone
+    // and do a conversion, but better leave this decision to the user of the library.
+    #[cfg(not(Py_LIMITED_API))]
+    let has_tzinfo = dt.get_tzinfo().is_some();
+    #[cfg(Py_LIMITED_API)]
+    let has_tzinfo = !dt.getattr(intern!(dt.py(), "tzinfo"))?.is_none();
+    if has_tzinfo {
+        return Err(PyTypeError::new_err("expected a datetime without tzinfo"));
+    }
 
-        // If the user tries to convert a timezone aware datetime into a naive one,
-        // we return a hard error. We could silently remove tzinfo, or assume local timezone
-        // and do a conversion, but better leave this decision to the user of the library.
+    if policy != FoldPolicy::Ignore {
         #[cfg(not(Py_LIMITED_API))]
-        let has_tzinfo = dt.get_tzinfo().is_some();
+        let fold = dt.get_fold();
         #[cfg(Py_LIMITED_API)]
-        let has_tzinfo = !dt.getattr(intern!(dt.py(), "tzinfo"))?.is_none();
-        if has_tzinfo {
-            return Err(PyTypeError::new_err("expected a datetime without tzinfo"));
+        let fold = dt.getattr(intern!(dt.py(), "fold"))?.extract::<u8>()? > 0;
+        if fold {
+            if policy == FoldPolicy::Error {
+                return Err(PyValueError::new_err(
+                    "discarded fold=1 of a naive datetime",
+                ));
+            }
+            let py = dt.py();
+            PyErr::warn(
+                py,
+                &py.get_type::<PyUserWarning>(),
+                ffi::c_str!("discarded fold=1 of a naive datetime"),
+                0,
+            )?;
         }
+    }
+
+    let time = with_extra_nanoseconds(dt, py_time_to_naive_time(dt)?)?;
+    let date = py_date_to_naive_date(dt)?;
+    let dt = PrimitiveDateTime::new(date, time);
+    Ok(dt)
+}
+
+/// Extracts the local wall clock of an aware `datetime.datetime` as a [`PrimitiveDateTime`], i.e.
+/// its components exactly as displayed, ignoring the UTC offset.
+///
+/// For example `2021-06-09 12:34:56+05:30` becomes `2021-06-09 12:34:56`. This differs from the
+/// [`FromPyObject`] implementation, which rejects aware datetimes, and from
+/// [`primitive_datetime_from_aware_utc`], which normalizes to UTC first. Naive datetimes are
+/// rejected with a `TypeError`.
+pub fn primitive_datetime_from_aware_wall_clock(
+    dt: &Bound<'_, PyAny>,
+) -> PyResult<PrimitiveDateTime> {
+    let dt: OffsetDateTime = dt.extract()?;
+    Ok(PrimitiveDateTime::new(dt.date(), dt.time()))
+}
+
+/// Extracts an aware `datetime.datetime` as a [`PrimitiveDateTime`] holding the UTC wall clock of
+/// the same instant, as when storing all datetimes as naive UTC in a database.
+///
+/// For example `2021-06-09 12:34:56+05:30` becomes `2021-06-09 07:04:56`. The offset is the one
+/// `utcoffset()` reports for this particular datetime, so named zones such as `zoneinfo.ZoneInfo`
+/// resolve DST (and `fold`) correctly. Naive datetimes are rejected with a `TypeError`, and a
+/// `ValueError` is raised if the UTC wall clock is not representable.
+pub fn primitive_datetime_from_aware_utc(dt: &Bound<'_, PyAny>) -> PyResult<PrimitiveDateTime> {
+    let dt: OffsetDateTime = dt.extract()?;
+    PrimitiveDateTime::new(dt.date(), dt.time())
+        .checked_sub(Duration::seconds(dt.offset().whole_seconds().into()))
+        .ok_or_else(|| PyValueError::new_err("datetime in UTC is out of range"))
+}
+
+/// Extracts all elements of a `pandas.DatetimeIndex`, or of any other iterable of naive
+/// datetimes.
+///
+/// A tz-naive `DatetimeIndex` is converted in bulk from its underlying `int64` values (`asi8`),
+/// without creating a Python object per element, and keeps full precision. These are in the
+/// index's `unit`, which is one of `"s"`, `"ms"`, `"us"` and `"ns"` since pandas 2.0, and always
+/// nanoseconds before. `NaT` elements raise a `ValueError`. Anything else, including a tz-aware
+/// `DatetimeIndex`, is iterated and each element extracted as a [`PrimitiveDateTime`].
+pub fn extract_primitive_datetimes_from_index(
+    ob: &Bound<'_, PyAny>,
+) -> PyResult<Vec<PrimitiveDateTime>> {
+    let py = ob.py();
+    if is_pandas_class(ob, "DatetimeIndex")? && ob.getattr(intern!(py, "tz"))?.is_none() {
+        let nanos_per_unit: i128 = if ob.hasattr(intern!(py, "unit"))? {
+            match ob.getattr(intern!(py, "unit"))?.to_string().as_str() {
+                "s" => 1_000_000_000,
+                "ms" => 1_000_000,
+                "us" => 1_000,
+                "ns" => 1,
+                unit => {
+                    return Err(PyValueError::new_err(format!(
+                        "unsupported DatetimeIndex unit {:?}",
+                        unit
+                    )))
+                }
+            }
+        } else {
+            1
+        };
+        let bytes = ob
+            .getattr(intern!(py, "asi8"))?
+            .call_method0(intern!(py, "tobytes"))?;
+        let bytes: &[u8] = bytes.downcast::<PyBytes>()?.as_bytes();
+        return bytes
+            .chunks_exact(8)
+            .enumerate()
+            .map(|(i, chunk)| {
+                let value = i64::from_ne_bytes(chunk.try_into().unwrap());
+                // pandas stores `NaT` as the smallest `int64`
+                if value == i64::MIN {
+                    return Err(PyValueError::new_err(format!(
+                        "NaT at position {} cannot be converted to PrimitiveDateTime",
+                        i
+                    )));
+                }
+                let nanos = i128::from(value) * nanos_per_unit;
+                let datetime = OffsetDateTime::from_unix_timestamp_nanos(nanos)
+                    .map_err(|_| PyValueError::new_err("epoch timestamp out of range"))?;
+                Ok(PrimitiveDateTime::new(datetime.date(), datetime.time()))
+            })
+            .collect();
+    }
+    ob.try_iter()?.map(|item| item?.extract()).collect()
+}
+
+/// Formats a [`Date`] like Python's `date.ctime()`, e.g. `"Wed Jun  9 00:00:00 2021"`.
+///
+/// The weekday and month abbreviations are always in English, independent of the current locale,
+/// and the day of the month is padded with a space.
+pub fn date_to_ctime_string(py: Python<'_>, date: Date) -> PyResult<Bound<'_, PyString>> {
+    primitive_datetime_to_ctime_string(py, date.midnight())
+}
+
+/// Formats a [`PrimitiveDateTime`] like Python's `datetime.ctime()`, e.g.
+/// `"Wed Jun  9 12:34:56 2021"`.
+///
+/// See [`date_to_ctime_string`] for details of the format.
+pub fn primitive_datetime_to_ctime_string(
+    py: Python<'_>,
+    datetime: PrimitiveDateTime,
+) -> PyResult<Bound<'_, PyString>> {
+    check_py_year(datetime.year())?;
+    let ctime = format!(
+        "{} {} {:>2} {:02}:{:02}:{:02} {:04}",
+        &datetime.weekday().to_string()[..3],
+        &datetime.month().to_string()[..3],
+        datetime.day(),
+        datetime.hour(),
+        datetime.minute(),
+        datetime.second(),
+        datetime.year(),
+    );
+    Ok(PyString::new(py, &ctime))
+}
+
+/// Formats a [`PrimitiveDateTime`] like Python's `datetime.isoformat(sep, timespec)` of the
+/// converted naive datetime, e.g. `"2021-06-09T12:34:56.789"` for [`TimeSpec::Milliseconds`].
+///
+/// Python's default separator is `'T'`.
+pub fn primitive_datetime_to_isoformat(
+    py: Python<'_>,
+    datetime: PrimitiveDateTime,
+    timespec: TimeSpec,
+    sep: char,
+) -> PyResult<Bound<'_, PyString>> {
+    check_py_year(datetime.year())?;
+    let mut iso = String::new();
+    write_iso_date(&mut iso, &datetime.date());
+    iso.push(sep);
+    write_iso_time(&mut iso, &datetime.time(), timespec);
+    Ok(PyString::new(py, &iso))
+}
+
+/// Builds a Python `datetime.datetime` from a [`Date`] and a [`Time`] by calling Python's
+/// `datetime.combine(date, time, tzinfo)`, so the result matches the stdlib exactly.
+///
+/// When `tzinfo` is `None` it is not passed at all, and Python uses the (always absent) `tzinfo`
+/// of the converted `time` as usual, giving a naive datetime.
+pub fn combine_into_py<'py>(
+    py: Python<'py>,
+    date: Date,
+    time: Time,
+    #[cfg(not(Py_LIMITED_API))] tzinfo: Option<&Bound<'py, PyTzInfo>>,
+    #[cfg(Py_LIMITED_API)] tzinfo: Option<&Bound<'py, PyAny>>,
+) -> PyResult<<PrimitiveDateTime as IntoPyObject<'py>>::Output> {
+    static COMBINE: InterpreterOnceCell<PyObject> = InterpreterOnceCell::new();
+    let combine = COMBINE
+        .get_or_try_init(py, || {
+            #[cfg(not(Py_LIMITED_API))]
+            let datetime = py.get_type::<PyDateTime>().into_any();
+            #[cfg(Py_LIMITED_API)]
+            let datetime = DatetimeTypes::try_get(py)?.datetime.bind(py).clone();
+            datetime.getattr(intern!(py, "combine")).map(Bound::unbind)
+        })?
+        .bind(py);
+    let date = date.into_pyobject(py)?;
+    let time = time.into_pyobject(py)?;
+    let datetime = match tzinfo {
+        Some(tzinfo) => combine.call1((date, time, tzinfo))?,
+        None => combine.call1((date, time))?,
+    };
+    #[cfg(not(Py_LIMITED_API))]
+    let datetime = datetime.downcast_into()?;
+    Ok(datetime)
+}
+
+/// Builds a Python `datetime.datetime` from all of its components, including `fold`, which
+/// disambiguates wall clock times that occur twice in `tzinfo`, e.g. when DST ends.
+///
+/// `fold` must be 0 or 1, as in Python. Without a `tzinfo` the result is naive, but still keeps
+/// the `fold`.
+pub fn datetime_from_parts<'py>(
+    py: Python<'py>,
+    date: Date,
+    time: Time,
+    #[cfg(not(Py_LIMITED_API))] tzinfo: Option<&Bound<'py, PyTzInfo>>,
+    #[cfg(Py_LIMITED_API)] tzinfo: Option<&Bound<'py, PyAny>>,
+    fold: u8,
+) -> PyResult<<PrimitiveDateTime as IntoPyObject<'py>>::Output> {
+    if fold > 1 {
+        return Err(PyValueError::new_err(format!(
+            "fold must be either 0 or 1, got {}",
+            fold
+        )));
+    }
+    check_py_year(date.year())?;
+    let DateArgs { year, month, day } = (&date).into();
+    let TimeArgs {
+        hour,
+        min,
+        sec,
+        micro,
+        truncated_leap_second,
+    } = (&time).into();
+
+    #[cfg(not(Py_LIMITED_API))]
+    let datetime = PyDateTime::new_with_fold(
+        py,
+        year,
+        month,
+        day,
+        hour,
+        min,
+        sec,
+        micro,
+        tzinfo,
+        fold == 1,
+    )?;
 
-        let dt = PrimitiveDateTime::new(py_date_to_naive_date(dt)?, py_time_to_naive_time(dt)?);
-        Ok(dt)
+    #[cfg(Py_LIMITED_API)]
+    let datetime = {
+        let kwargs = [(intern!(py, "fold"), fold)].into_py_dict(py)?;
+        DatetimeTypes::try_get(py)?.datetime.bind(py).call(
+            (year, month, day, hour, min, sec, micro, tzinfo),
+            Some(&kwargs),
+        )?
+    };
+
+    if truncated_leap_second {
+        warn_truncated_leap_second(&datetime);
     }
+
+    Ok(datetime)
 }
 
 #[allow(deprecated)]
@@ -481,25 +1424,7 @@ impl<'py> IntoPyObject<'py> for &OffsetDateTime {
     type Error = PyErr;
 
     fn into_pyobject(self, py: Python<'py>) -> Result<Self::Output, Self::Error> {
-        let tz = self.offset().into_pyobject(py)?;
-        let DateArgs { year, month, day } = (&self.date()).into();
-        let TimeArgs {
-            hour,
-            min,
-            sec,
-            micro,
-            truncated_leap_second,
-        } = (&self.time()).into();
-
-        #[cfg(not(Py_LIMITED_API))]
-        let datetime = PyDateTime::new(py, year, month, day, hour, min, sec, micro, Some(&tz))?;
-
-        #[cfg(Py_LIMITED_API)]
-        let datetime = DatetimeTypes::try_get(py).and_then(|dt| {
-            dt.datetime
-                .bind(py)
-                .call1((year, month, day, hour, min, sec, micro, tz))
-        })?;
+        let (datetime, truncated_leap_second) = offset_datetime_to_py_datetime(py, self)?;
 
         if truncated_leap_second {
             warn_truncated_leap_second(&datetime);
@@ -509,27 +1434,301 @@ impl<'py> IntoPyObject<'py> for &OffsetDateTime {
     }
 }
 
-impl FromPyObject<'_> for OffsetDateTime {
-    fn extract_bound(dt: &Bound<'_, PyAny>) -> PyResult<OffsetDateTime> {
-        #[cfg(not(Py_LIMITED_API))]
-        let dt = dt.downcast::<PyDateTime>()?;
-        #[cfg(Py_LIMITED_API)]
-        check_type(dt, &DatetimeTypes::get(dt.py()).datetime, "PyDateTime")?;
-
+/// Converts an [`OffsetDateTime`] to an aware Python `datetime.datetime` whose fixed offset
+/// `datetime.timezone` reports `tzname` from `tzname()`, e.g. `"IST"`, instead of the
+/// `"UTC+05:30"` style name Python generates otherwise.
+pub fn offset_datetime_into_py_named<'py>(
+    py: Python<'py>,
+    datetime: &OffsetDateTime,
+    tzname: &str,
+) -> PyResult<<OffsetDateTime as IntoPyObject<'py>>::Output> {
+    if tzname.is_empty() {
+        return Err(PyValueError::new_err("tzname must not be empty"));
+    }
+    check_py_offset(datetime.offset())?;
+    let td = Duration::seconds(datetime.offset().whole_seconds().into()).into_pyobject(py)?;
+    let tz = timezone_type(py)?.call1((td, tzname))?;
+    #[cfg(not(Py_LIMITED_API))]
+    let tz = tz.downcast_into()?;
+    let (datetime, truncated_leap_second) =
+        offset_datetime_to_py_datetime_with_tz(py, datetime, &tz)?;
+    if truncated_leap_second {
+        warn_truncated_leap_second(&datetime);
+    }
+    Ok(datetime)
+}
+
+/// Converts an [`OffsetDateTime`] to a Python `datetime.datetime`, also returning whether
+/// precision was lost, like [`time_into_py_reporting`].
+pub fn offset_datetime_into_py_reporting<'py>(
+    py: Python<'py>,
+    datetime: &OffsetDateTime,
+) -> PyResult<(<OffsetDateTime as IntoPyObject<'py>>::Output, bool)> {
+    let (py_datetime, truncated_leap_second) = offset_datetime_to_py_datetime(py, datetime)?;
+    Ok((
+        py_datetime,
+        truncated_leap_second || has_sub_microseconds(&datetime.time()),
+    ))
+}
+
+/// Converts an [`OffsetDateTime`] to a Python tuple `(datetime, extra_nanos)`, where
+/// `extra_nanos` is an `int` in `0..=999` holding the nanoseconds below the `datetime`'s
+/// microseconds.
+///
+/// Unlike the plain `datetime`, this represents every [`OffsetDateTime`] without loss using only
+/// standard library types. See [`offset_datetime_from_py_with_nanos`] for the reverse conversion.
+pub fn offset_datetime_into_py_with_nanos<'py>(
+    py: Python<'py>,
+    datetime: &OffsetDateTime,
+) -> PyResult<Bound<'py, PyTuple>> {
+    let extra_nanos = datetime.nanosecond() % 1000;
+    (datetime.into_pyobject(py)?, extra_nanos).into_pyobject(py)
+}
+
+/// Extracts an [`OffsetDateTime`] from a `(datetime, extra_nanos)` tuple as produced by
+/// [`offset_datetime_into_py_with_nanos`], adding `extra_nanos` to the `datetime`'s microseconds.
+///
+/// `extra_nanos` replaces any part below the microsecond that the `datetime` carries itself, like
+/// the `nanosecond` attribute of a `pandas.Timestamp`. A `ValueError` is raised if `extra_nanos`
+/// is not in `0..=999`.
+pub fn offset_datetime_from_py_with_nanos(ob: &Bound<'_, PyAny>) -> PyResult<OffsetDateTime> {
+    let (datetime, extra_nanos): (OffsetDateTime, i64) = ob.extract()?;
+    if !(0..1000).contains(&extra_nanos) {
+        return Err(PyValueError::new_err(format!(
+            "extra_nanos must be in 0..=999, got {}",
+            extra_nanos
+        )));
+    }
+    datetime
+        .replace_nanosecond(datetime.microsecond() * 1000 + extra_nanos as u32)
+        .map_err(|_| PyValueError::new_err("invalid or out-of-range time"))
+}
+
+/// Counts of the precision lost by [`convert_offset_datetimes_with_stats`].
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Hash, Default)]
+pub struct ConversionStats {
+    /// The number of converted elements.
+    pub converted: usize,
+    /// The number of elements whose leap second was truncated.
+    pub truncated_leap_seconds: usize,
+    /// The number of elements whose part below Python's microsecond resolution was truncated.
+    pub truncated_sub_microseconds: usize,
+}
+
+/// Converts a batch of [`OffsetDateTime`]s to a Python `list` of `datetime.datetime`s, counting
+/// the elements which lost precision.
+///
+/// Instead of warning once per element about a truncated leap second, as the regular conversion
+/// does, this only reports the counts in the returned [`ConversionStats`].
+pub fn convert_offset_datetimes_with_stats<'py>(
+    py: Python<'py>,
+    datetimes: &[OffsetDateTime],
+) -> PyResult<(Bound<'py, PyList>, ConversionStats)> {
+    let mut stats = ConversionStats::default();
+    let mut converted = Vec::with_capacity(datetimes.len());
+    for datetime in datetimes {
+        let (py_datetime, truncated_leap_second) = offset_datetime_to_py_datetime(py, datetime)?;
+        stats.converted += 1;
+        stats.truncated_leap_seconds += usize::from(truncated_leap_second);
+        stats.truncated_sub_microseconds += usize::from(has_sub_microseconds(&datetime.time()));
+        converted.push(py_datetime);
+    }
+    Ok((PyList::new(py, converted)?, stats))
+}
+
+/// Converts an [`OffsetDateTime`] to a Python `datetime.datetime` in the `zoneinfo.ZoneInfo`
+/// time zone with key `zone`, e.g. `"Europe/London"`, representing the same instant.
+///
+/// The wall clock and `fold` are computed by Python's `astimezone`, so an instant in a DST
+/// fall-back window gets `fold=1` if it is the second occurrence of its wall clock time. The
+/// result thus converts back to the same instant. See [`zoneinfo_from_key`] for the errors for an
+/// unknown `zone`.
+pub fn offset_datetime_into_zoneinfo_with_fold<'py>(
+    py: Python<'py>,
+    datetime: &OffsetDateTime,
+    zone: &str,
+) -> PyResult<<OffsetDateTime as IntoPyObject<'py>>::Output> {
+    let zone = zoneinfo_from_key(py, zone)?;
+    let datetime = datetime
+        .into_pyobject(py)?
+        .call_method1(intern!(py, "astimezone"), (zone,))?;
+    #[cfg(not(Py_LIMITED_API))]
+    let datetime = datetime.downcast_into()?;
+    Ok(datetime)
+}
+
+/// Attaches `tzinfo` to the Python `datetime.datetime` `dt` without changing its wall clock time,
+/// like Python's `dt.replace(tzinfo=tzinfo)`. `None` makes the result naive.
+///
+/// Unlike [`offset_datetime_into_zoneinfo_with_fold`] and `astimezone`, which convert to the
+/// same instant in another zone, this reinterprets the wall clock time in the new zone, so the
+/// result generally represents a different instant.
+pub fn offset_datetime_replace_tzinfo<'py>(
+    py: Python<'py>,
+    dt: &Bound<'py, PyAny>,
+    #[cfg(not(Py_LIMITED_API))] tzinfo: Option<&Bound<'py, PyTzInfo>>,
+    #[cfg(Py_LIMITED_API)] tzinfo: Option<&Bound<'py, PyAny>>,
+) -> PyResult<<OffsetDateTime as IntoPyObject<'py>>::Output> {
+    let kwargs = PyDict::new(py);
+    kwargs.set_item(intern!(py, "tzinfo"), tzinfo)?;
+    let datetime = dt.call_method(intern!(py, "replace"), (), Some(&kwargs))?;
+    #[cfg(not(Py_LIMITED_API))]
+    let datetime = datetime.downcast_into()?;
+    Ok(datetime)
+}
+
+/// Converts an [`OffsetDateTime`] to a Python `datetime.datetime` in the system's local time
+/// zone representing the same instant, like Python's `datetime.astimezone()` without arguments.
+///
+/// The local time zone is resolved by Python, so the result depends on the interpreter's time zone
+/// configuration, e.g. the `TZ` environment variable and `time.tzset()`. Its `tzinfo` is a fixed
+/// offset `datetime.timezone` named after the local zone, such as `"CEST"`.
+pub fn offset_datetime_into_py_local<'py>(
+    py: Python<'py>,
+    datetime: &OffsetDateTime,
+) -> PyResult<<OffsetDateTime as IntoPyObject<'py>>::Output> {
+    let datetime = datetime
+        .into_pyobject(py)?
+        .call_method0(intern!(py, "astimezone"))?;
+    #[cfg(not(Py_LIMITED_API))]
+    let datetime = datetime.downcast_into()?;
+    Ok(datetime)
+}
+
+/// Converts an [`OffsetDateTime`] to a Python `dict` holding its components separately: a
+/// `datetime.date` under `"date"`, a naive `datetime.time` under `"time"` and the UTC offset in
+/// whole seconds as an `int` under `"offset"`.
+///
+/// See [`offset_datetime_from_py_parts_dict`] for the reverse conversion.
+pub fn offset_datetime_into_py_parts_dict<'py>(
+    py: Python<'py>,
+    datetime: &OffsetDateTime,
+) -> PyResult<Bound<'py, PyDict>> {
+    let dict = PyDict::new(py);
+    dict.set_item(intern!(py, "date"), datetime.date().into_pyobject(py)?)?;
+    dict.set_item(intern!(py, "time"), datetime.time().into_pyobject(py)?)?;
+    dict.set_item(intern!(py, "offset"), datetime.offset().whole_seconds())?;
+    Ok(dict)
+}
+
+/// Extracts an [`OffsetDateTime`] from a `dict` as produced by
+/// [`offset_datetime_into_py_parts_dict`].
+///
+/// A missing key raises a `KeyError`, and an offset outside of what [`UtcOffset`] supports (just
+/// under 26 hours in either direction) a `ValueError`.
+pub fn offset_datetime_from_py_parts_dict(dict: &Bound<'_, PyDict>) -> PyResult<OffsetDateTime> {
+    let get = |key: &'static str| dict.get_item(key)?.ok_or_else(|| PyKeyError::new_err(key));
+    let date: Date = get("date")?.extract()?;
+    let time: Time = get("time")?.extract()?;
+    let offset: i32 = get("offset")?.extract()?;
+    let offset = UtcOffset::from_whole_seconds(offset)
+        .map_err(|_| PyValueError::new_err(format!("offset {} is out of range", offset)))?;
+    Ok(PrimitiveDateTime::new(date, time).assume_offset(offset))
+}
+
+/// A Python iterator lazily converting the [`OffsetDateTime`]s produced by a Rust iterator.
+///
+/// Each element is only converted to a `datetime.datetime` when Python calls `__next__`, so large
+/// sequences never have to be materialized as a `list`. The leap-second truncation warning is
+/// emitted at most once per iterator rather than once per element.
+#[cfg(feature = "macros")]
+#[crate::pyclass(crate = "crate")]
+pub struct OffsetDateTimeIterator {
+    iter: Box<dyn Iterator<Item = OffsetDateTime> + Send>,
+    warned_leap_second: bool,
+}
+
+#[cfg(feature = "macros")]
+impl OffsetDateTimeIterator {
+    /// Wraps a Rust iterator of [`OffsetDateTime`]s.
+    pub fn new<I>(iter: I) -> Self
+    where
+        I: IntoIterator<Item = OffsetDateTime>,
+        I::IntoIter: Send + 'static,
+    {
+        Self {
+            iter: Box::new(iter.into_iter()),
+            warned_leap_second: false,
+        }
+    }
+
+    /// Warns about a truncated leap second, unless this iterator already did.
+    fn warn_truncated_leap_second_once(&mut self, datetime: &Bound<'_, PyAny>) {
+        if !self.warned_leap_second {
+            self.warned_leap_second = true;
+            warn_truncated_leap_second(datetime);
+        }
+    }
+}
+
+#[cfg(feature = "macros")]
+#[crate::pymethods(crate = "crate")]
+impl OffsetDateTimeIterator {
+    fn __iter__(slf: crate::PyRef<'_, Self>) -> crate::PyRef<'_, Self> {
+        slf
+    }
+
+    fn __next__<'py>(&mut self, py: Python<'py>) -> PyResult<Option<Bound<'py, PyAny>>> {
+        let dt = match self.iter.next() {
+            Some(dt) => dt,
+            None => return Ok(None),
+        };
+        let (datetime, truncated_leap_second) = offset_datetime_to_py_datetime(py, &dt)?;
+        let datetime = datetime.into_any();
+        if truncated_leap_second {
+            self.warn_truncated_leap_second_once(&datetime);
+        }
+        Ok(Some(datetime))
+    }
+}
+
+/// Extracts from an aware Python `datetime.datetime`. As for [`Time`], the result never holds a
+/// leap second.
+///
+/// Objects of third-party datetime libraries are accepted as well: subclasses of `datetime`
+/// (e.g. pendulum's) directly, wrappers exposing the wrapped `datetime` as a `datetime` attribute
+/// (e.g. arrow's), and otherwise any object with the `year`, `month`, `day`, `hour`, `minute`,
+/// `second` and `microsecond` attributes and a `utcoffset()` method.
+impl FromPyObject<'_> for OffsetDateTime {
+    fn extract_bound(ob: &Bound<'_, PyAny>) -> PyResult<OffsetDateTime> {
+        #[cfg(not(Py_LIMITED_API))]
+        let dt = match ob.downcast::<PyDateTime>() {
+            Ok(dt) => dt,
+            Err(err) => return extract_offset_datetime_like(ob)?.ok_or_else(|| err.into()),
+        };
+        #[cfg(Py_LIMITED_API)]
+        let dt = match check_type(ob, &DatetimeTypes::get(ob.py()).datetime, "PyDateTime") {
+            Ok(()) => ob,
+            Err(err) => return extract_offset_datetime_like(ob)?.ok_or(err),
+        };
+
         #[cfg(not(Py_LIMITED_API))]
         let tzinfo = dt.get_tzinfo();
         #[cfg(Py_LIMITED_API)]
         let tzinfo: Option<Bound<'_, PyAny>> = dt.getattr(intern!(dt.py(), "tzinfo"))?.extract()?;
 
-        let tz = if let Some(tzinfo) = tzinfo {
-            tzinfo.extract()?
-        } else {
-            return Err(PyTypeError::new_err(
-                "expected a datetime with non-None tzinfo",
-            ));
+        let tzinfo = match tzinfo {
+            Some(tzinfo) => tzinfo,
+            None => {
+                return Err(PyTypeError::new_err(
+                    "expected a datetime with non-None tzinfo",
+                ))
+            }
         };
-        let naive_dt =
-            PrimitiveDateTime::new(py_date_to_naive_date(dt)?, py_time_to_naive_time(dt)?);
+        // Asking the datetime (rather than its tzinfo) for the offset lets Python apply its own
+        // semantics: `fold` is taken into account by DST-aware zones such as `zoneinfo.ZoneInfo`,
+        // and ignored by fixed offset zones such as `datetime.timezone`.
+        let py_timedelta = dt.call_method0(intern!(dt.py(), "utcoffset"))?;
+        if py_timedelta.is_none() {
+            return Err(PyTypeError::new_err(format!(
+                "{} returned None from utcoffset()",
+                tzinfo.repr()?
+            )));
+        }
+        let tz = py_timedelta_to_utc_offset(&py_timedelta)?;
+        let time = with_extra_nanoseconds(dt, py_time_to_naive_time(dt)?)?;
+        let date = py_date_to_naive_date(dt)?;
+        let naive_dt = PrimitiveDateTime::new(date, time);
         Ok(naive_dt.assume_offset(tz))
         // .ok_or_else(|| {
         //     PyValueError::new_err(format!(
@@ -540,6 +1739,670 @@ impl FromPyObject<'_> for OffsetDateTime {
     }
 }
 
+/// Extracts an [`OffsetDateTime`] from an object which is not a `datetime.datetime` but wraps or
+/// mimics one, returning `None` if it does neither.
+fn extract_offset_datetime_like(ob: &Bound<'_, PyAny>) -> PyResult<Option<OffsetDateTime>> {
+    let py = ob.py();
+    if ob.hasattr(intern!(py, "datetime"))? {
+        let inner = ob.getattr(intern!(py, "datetime"))?;
+        #[cfg(not(Py_LIMITED_API))]
+        let is_datetime = inner.is_instance_of::<PyDateTime>();
+        #[cfg(Py_LIMITED_API)]
+        let is_datetime = inner.is_instance(DatetimeTypes::try_get(py)?.datetime.bind(py))?;
+        if is_datetime {
+            return inner.extract().map(Some);
+        }
+    }
+
+    let fields = [
+        intern!(py, "year"),
+        intern!(py, "month"),
+        intern!(py, "day"),
+        intern!(py, "hour"),
+        intern!(py, "minute"),
+        intern!(py, "second"),
+        intern!(py, "microsecond"),
+        intern!(py, "utcoffset"),
+    ];
+    for field in fields {
+        if !ob.hasattr(field)? {
+            return Ok(None);
+        }
+    }
+
+    let date = date_from_accessors(ob)?;
+    let time = Time::from_hms_micro(
+        ob.getattr(intern!(py, "hour"))?.extract()?,
+        ob.getattr(intern!(py, "minute"))?.extract()?,
+        ob.getattr(intern!(py, "second"))?.extract()?,
+        extract_microsecond(ob)?,
+    )
+    .map_err(|_| PyValueError::new_err("invalid or out-of-range time"))?;
+    let time = with_extra_nanoseconds(ob, time)?;
+    let py_timedelta = ob.call_method0(intern!(py, "utcoffset"))?;
+    if py_timedelta.is_none() {
+        return Err(PyTypeError::new_err(format!(
+            "{} returned None from utcoffset()",
+            ob.repr()?
+        )));
+    }
+    let offset = py_timedelta_to_utc_offset(&py_timedelta)?;
+    Ok(Some(
+        PrimitiveDateTime::new(date, time).assume_offset(offset),
+    ))
+}
+
+/// Extracts the half-open range of datetimes from `start` up to, but excluding, `stop` in
+/// increments of `step`, like a basic `pandas.date_range`.
+///
+/// All generated datetimes share the UTC offset of `start`. A zero or negative `step` is rejected
+/// with a `ValueError`.
+pub fn extract_offset_datetime_range(
+    start: &Bound<'_, PyAny>,
+    stop: &Bound<'_, PyAny>,
+    #[cfg(not(Py_LIMITED_API))] step: &Bound<'_, PyDelta>,
+    #[cfg(Py_LIMITED_API)] step: &Bound<'_, PyAny>,
+) -> PyResult<Vec<OffsetDateTime>> {
+    let start: OffsetDateTime = start.extract()?;
+    let stop: OffsetDateTime = stop.extract()?;
+    let step: Duration = step.extract()?;
+    if !step.is_positive() {
+        return Err(PyValueError::new_err("step must be a positive timedelta"));
+    }
+    let mut range = Vec::new();
+    let mut current = start;
+    while current < stop {
+        range.push(current);
+        current = match current.checked_add(step) {
+            Some(next) => next,
+            None => break,
+        };
+    }
+    Ok(range)
+}
+
+/// Extracts an [`OffsetDateTime`] together with the original `tzinfo` of the Python datetime.
+///
+/// The extracted [`OffsetDateTime`] only keeps the UTC offset, so holding on to the `tzinfo`
+/// allows reattaching it later, e.g. to preserve the identity of a `zoneinfo.ZoneInfo`.
+pub fn offset_datetime_extract_with_tzinfo(
+    dt: &Bound<'_, PyAny>,
+) -> PyResult<(OffsetDateTime, PyObject)> {
+    let datetime: OffsetDateTime = dt.extract()?;
+    let tzinfo = dt.getattr(intern!(dt.py(), "tzinfo"))?;
+    Ok((datetime, tzinfo.unbind()))
+}
+
+/// Extracts an [`OffsetDateTime`] like the [`FromPyObject`] implementation, additionally returning
+/// whether the wall clock time of `dt` does not exist in its time zone, because it falls into a
+/// gap such as the one skipped when DST starts.
+///
+/// Python still resolves an offset for such a time (based on `fold`), and so does the returned
+/// [`OffsetDateTime`]; the flag lets callers who care about data quality detect that the local
+/// time never actually occurred. Fixed offset zones never have gaps.
+pub fn offset_datetime_extract_with_gap_flag(
+    dt: &Bound<'_, PyAny>,
+) -> PyResult<(OffsetDateTime, bool)> {
+    let py = dt.py();
+    let datetime: OffsetDateTime = dt.extract()?;
+    // Following PEP 495, a time is in a gap if its offset with `fold=0` is smaller than with
+    // `fold=1`. Unlike a round trip through UTC, this can't overflow near the range edges.
+    let offset = |fold: u8| -> PyResult<Option<Duration>> {
+        let kwargs = [(intern!(py, "fold"), fold)].into_py_dict(py)?;
+        dt.call_method(intern!(py, "replace"), (), Some(&kwargs))?
+            .call_method0(intern!(py, "utcoffset"))?
+            .extract()
+    };
+    let in_gap = offset(0)? < offset(1)?;
+    Ok((datetime, in_gap))
+}
+
+/// Why the wall clock time passed to the resolver of [`offset_datetime_extract_with_resolver`]
+/// has no single offset.
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Hash)]
+pub enum AmbiguityKind {
+    /// The time occurs twice, e.g. when DST ends and the clocks go back.
+    Ambiguous,
+    /// The time doesn't occur at all, e.g. when DST starts and the clocks skip forward.
+    Nonexistent,
+}
+
+/// Python's `fold`, choosing which offset applies to an ambiguous or nonexistent time.
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Hash)]
+pub enum Fold {
+    /// `fold=0`: the offset in effect before the transition, i.e. the earlier of two ambiguous
+    /// times.
+    Zero,
+    /// `fold=1`: the offset in effect after the transition, i.e. the later of two ambiguous
+    /// times.
+    One,
+}
+
+/// Extracts an [`OffsetDateTime`] like the [`FromPyObject`] implementation, but lets `resolver`
+/// choose the [`Fold`] for ambiguous and nonexistent wall clock times instead of using the
+/// `fold` attribute of `dt`.
+///
+/// `resolver` is only called for such times, which can only occur with zones that have
+/// transitions, such as `zoneinfo.ZoneInfo`. Following PEP 495, a time is ambiguous if its offset
+/// with `fold=0` is larger than with `fold=1`, and nonexistent if it is smaller.
+pub fn offset_datetime_extract_with_resolver(
+    dt: &Bound<'_, PyAny>,
+    resolver: impl Fn(AmbiguityKind) -> Fold,
+) -> PyResult<OffsetDateTime> {
+    let py = dt.py();
+    let with_fold = |fold: u8| -> PyResult<Bound<'_, PyAny>> {
+        let kwargs = [(intern!(py, "fold"), fold)].into_py_dict(py)?;
+        dt.call_method(intern!(py, "replace"), (), Some(&kwargs))
+    };
+    let (earlier, later) = (with_fold(0)?, with_fold(1)?);
+    let earlier_offset: Option<Duration> =
+        earlier.call_method0(intern!(py, "utcoffset"))?.extract()?;
+    let later_offset: Option<Duration> = later.call_method0(intern!(py, "utcoffset"))?.extract()?;
+    let kind = match earlier_offset.cmp(&later_offset) {
+        std::cmp::Ordering::Equal => return dt.extract(),
+        std::cmp::Ordering::Greater => AmbiguityKind::Ambiguous,
+        std::cmp::Ordering::Less => AmbiguityKind::Nonexistent,
+    };
+    match resolver(kind) {
+        Fold::Zero => earlier.extract(),
+        Fold::One => later.extract(),
+    }
+}
+
+/// Extracts an [`OffsetDateTime`] from untrusted data, e.g. unpickled objects, without running
+/// arbitrary Python code.
+///
+/// Only a `datetime.datetime` (not a subclass) whose `tzinfo` is exactly a `datetime.timezone` or
+/// a `zoneinfo.ZoneInfo` is accepted, since custom `tzinfo` classes may run any code in their
+/// `utcoffset()`. Anything else raises a `TypeError` before any of its methods are called.
+pub fn offset_datetime_extract_trusted(dt: &Bound<'_, PyAny>) -> PyResult<OffsetDateTime> {
+    let py = dt.py();
+    if !dt.get_type().is(&DatetimeClass::DateTime.builtin(py)?) {
+        return Err(PyTypeError::new_err(format!(
+            "expected exactly datetime.datetime, got {}",
+            dt.get_type()
+        )));
+    }
+    let tzinfo = dt.getattr(intern!(py, "tzinfo"))?;
+    let tzinfo_type = tzinfo.get_type();
+    // Only look for `ZoneInfo` if needed, since importing `zoneinfo` can fail
+    let trusted = tzinfo_type.is(timezone_type(py)?)
+        || (!tzinfo.is_none() && zoneinfo_type(py).map_or(false, |zi| tzinfo_type.is(zi)));
+    if !trusted {
+        return Err(PyTypeError::new_err(format!(
+            "untrusted tzinfo type {}, expected datetime.timezone or zoneinfo.ZoneInfo",
+            tzinfo_type
+        )));
+    }
+    dt.extract()
+}
+
+/// Extracts an [`OffsetDateTime`], returning `default` instead if `dt` is `None`.
+///
+/// This is meant for lenient deserialization, where a missing datetime is replaced by a sentinel
+/// such as [`OffsetDateTime::UNIX_EPOCH`]. Anything other than `None` is extracted as usual.
+pub fn offset_datetime_extract_or_default(
+    dt: &Bound<'_, PyAny>,
+    default: OffsetDateTime,
+) -> PyResult<OffsetDateTime> {
+    if dt.is_none() {
+        Ok(default)
+    } else {
+        dt.extract()
+    }
+}
+
+/// An [`OffsetDateTime`] which can additionally be extracted from a Python `int` of seconds since
+/// the Unix epoch, interpreted as UTC.
+///
+/// Extraction first tries the regular [`OffsetDateTime`] conversion from an aware
+/// `datetime.datetime`, and only falls back to the integer interpretation when the object is an
+/// `int`. Python's `bool` is a subclass of `int`, but is rejected rather than read as 0 or 1.
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Hash)]
+pub struct EpochSeconds(pub OffsetDateTime);
+
+impl FromPyObject<'_> for EpochSeconds {
+    fn extract_bound(ob: &Bound<'_, PyAny>) -> PyResult<EpochSeconds> {
+        match ob.extract::<OffsetDateTime>() {
+            Ok(dt) => Ok(EpochSeconds(dt)),
+            Err(_) if ob.is_instance_of::<PyInt>() && !ob.is_instance_of::<PyBool>() => {
+                let timestamp: i64 = ob.extract()?;
+                OffsetDateTime::from_unix_timestamp(timestamp)
+                    .map(EpochSeconds)
+                    .map_err(|_| PyValueError::new_err("epoch timestamp out of range"))
+            }
+            Err(err) => Err(err),
+        }
+    }
+}
+
+/// Computes the naive UTC wall clock of a float Unix timestamp, as Python's deprecated
+/// `datetime.utcfromtimestamp(ts)` does.
+///
+/// This is the replacement for code relying on the old behavior, and is computed entirely in Rust
+/// without calling the deprecated method. As in Python, the fractional part of `ts` is rounded to
+/// the nearest microsecond with ties to even, negative timestamps are before the epoch, and
+/// timestamps outside of Python's year range or which are not finite raise a `ValueError`.
+pub fn primitive_datetime_from_unix_float_utc(
+    _py: Python<'_>,
+    ts: f64,
+) -> PyResult<PrimitiveDateTime> {
+    let datetime = unix_float_to_utc(ts)?;
+    Ok(PrimitiveDateTime::new(datetime.date(), datetime.time()))
+}
+
+/// Converts a float Unix timestamp, e.g. from Python's `time.time()`, to an [`OffsetDateTime`] in
+/// UTC with the same microsecond as Python's `datetime.fromtimestamp(ts, timezone.utc)`.
+///
+/// Scaling the whole timestamp with `ts * 1e6` is off by a microsecond for many values, since the
+/// product is rounded to the float precision first. Instead the integer seconds are split off and
+/// only the fraction is scaled and rounded to the nearest microsecond, with ties to even as in
+/// Python. Timestamps which are not finite or outside of Python's year range raise a
+/// `ValueError`.
+pub fn offset_datetime_from_unix_float(_py: Python<'_>, ts: f64) -> PyResult<OffsetDateTime> {
+    unix_float_to_utc(ts)
+}
+
+fn unix_float_to_utc(ts: f64) -> PyResult<OffsetDateTime> {
+    if !ts.is_finite() {
+        return Err(PyValueError::new_err(format!(
+            "timestamp must be finite, got {}",
+            ts
+        )));
+    }
+    // Rounding the fractional part separately keeps full precision for large timestamps
+    let secs = ts.trunc();
+    let scaled = (ts - secs) * 1e6;
+    let mut micros = scaled.round();
+    if (scaled - scaled.trunc()).abs() == 0.5 && micros % 2.0 != 0.0 {
+        micros -= scaled.signum();
+    }
+    let out_of_range = || PyValueError::new_err(format!("timestamp {} is out of range", ts));
+    // Larger timestamps are far outside of Python's year range anyway
+    if secs.abs() > 1e15 {
+        return Err(out_of_range());
+    }
+    let nanos = secs as i128 * 1_000_000_000 + micros as i128 * 1_000;
+    let datetime = OffsetDateTime::from_unix_timestamp_nanos(nanos).map_err(|_| out_of_range())?;
+    if !PY_YEAR_RANGE.contains(&datetime.year()) {
+        return Err(out_of_range());
+    }
+    Ok(datetime)
+}
+
+/// Converts an [`OffsetDateTime`] to a Python `int` of microseconds since the Unix epoch, as stored
+/// by Parquet's `TIMESTAMP(MICROS)` logical type.
+///
+/// Sub-microsecond precision is truncated to the earlier microsecond, like the conversion to a
+/// Python `datetime`. An `i64` of microseconds covers roughly 292,000 years on either side of the
+/// epoch, so every [`OffsetDateTime`] fits unless the `large-dates` feature is enabled; an
+/// `OverflowError` is raised for the years beyond.
+pub fn offset_datetime_to_parquet_micros<'py>(
+    py: Python<'py>,
+    datetime: &OffsetDateTime,
+) -> PyResult<Bound<'py, PyInt>> {
+    let micros: i64 = datetime
+        .unix_timestamp_nanos()
+        .div_euclid(1000)
+        .try_into()
+        .map_err(|_| PyOverflowError::new_err("datetime does not fit in i64 microseconds"))?;
+    Ok(micros.into_pyobject(py)?)
+}
+
+/// Converts an [`OffsetDateTime`] to a Python `int` of nanoseconds since the Unix epoch, as stored
+/// by Parquet's `TIMESTAMP(NANOS)` logical type.
+///
+/// An `i64` of nanoseconds only covers 1677-09-21 00:12:43.145224192 to
+/// 2262-04-11 23:47:16.854775807 UTC; an `OverflowError` is raised outside of that range.
+pub fn offset_datetime_to_parquet_nanos<'py>(
+    py: Python<'py>,
+    datetime: &OffsetDateTime,
+) -> PyResult<Bound<'py, PyInt>> {
+    let nanos: i64 = datetime
+        .unix_timestamp_nanos()
+        .try_into()
+        .map_err(|_| PyOverflowError::new_err("datetime does not fit in i64 nanoseconds"))?;
+    Ok(nanos.into_pyobject(py)?)
+}
+
+/// Extracts an [`OffsetDateTime`] in UTC from a Python `int` of microseconds since the Unix
+/// epoch, as produced by [`offset_datetime_to_parquet_micros`].
+pub fn offset_datetime_from_parquet_micros(ob: &Bound<'_, PyAny>) -> PyResult<OffsetDateTime> {
+    let micros: i64 = ob.extract()?;
+    OffsetDateTime::from_unix_timestamp_nanos(i128::from(micros) * 1000)
+        .map_err(|_| PyValueError::new_err("epoch timestamp out of range"))
+}
+
+/// Extracts an [`OffsetDateTime`] in UTC from a Python `int` of nanoseconds since the Unix epoch,
+/// as produced by [`offset_datetime_to_parquet_nanos`].
+pub fn offset_datetime_from_parquet_nanos(ob: &Bound<'_, PyAny>) -> PyResult<OffsetDateTime> {
+    let nanos: i64 = ob.extract()?;
+    OffsetDateTime::from_unix_timestamp_nanos(nanos.into())
+        .map_err(|_| PyValueError::new_err("epoch timestamp out of range"))
+}
+
+/// Converts a count of nanoseconds since the Unix epoch, as returned by Python's `time.time_ns()`,
+/// to an [`OffsetDateTime`] in UTC.
+///
+/// Unlike going through a Python `datetime`, this keeps full nanosecond precision. A `ValueError`
+/// is raised if `ns` is outside of the range of [`OffsetDateTime`].
+pub fn offset_datetime_from_time_ns(_py: Python<'_>, ns: i128) -> PyResult<OffsetDateTime> {
+    OffsetDateTime::from_unix_timestamp_nanos(ns)
+        .map_err(|_| PyValueError::new_err("epoch timestamp out of range"))
+}
+
+/// Converts an [`OffsetDateTime`] to a Python `int` of nanoseconds since the Unix epoch, in the
+/// format of Python's `time.time_ns()`. This is lossless, and the inverse of
+/// [`offset_datetime_from_time_ns`].
+pub fn offset_datetime_to_time_ns<'py>(
+    py: Python<'py>,
+    datetime: &OffsetDateTime,
+) -> PyResult<Bound<'py, PyInt>> {
+    Ok(datetime.unix_timestamp_nanos().into_pyobject(py)?)
+}
+
+/// Formats an [`OffsetDateTime`] like Python's `datetime.isoformat()` of the converted datetime,
+/// e.g. `"2021-06-09T12:34:56.789012+05:30"`.
+///
+/// Like Python, the fractional seconds are omitted when the microsecond is zero. Setting
+/// `always_microseconds` always includes all six digits instead, as Python does for
+/// `isoformat(timespec="microseconds")`, which is useful for systems that need fixed-width
+/// timestamps. Sub-microsecond precision is truncated.
+pub fn offset_datetime_to_isoformat<'py>(
+    py: Python<'py>,
+    datetime: &OffsetDateTime,
+    always_microseconds: bool,
+) -> PyResult<Bound<'py, PyString>> {
+    check_py_year(datetime.year())?;
+    let mut iso = String::new();
+    write_iso_date(&mut iso, &datetime.date());
+    iso.push('T');
+    let timespec = if always_microseconds {
+        TimeSpec::Microseconds
+    } else {
+        TimeSpec::Auto
+    };
+    write_iso_time(&mut iso, &datetime.time(), timespec);
+    write_iso_offset(&mut iso, datetime.offset());
+    Ok(PyString::new(py, &iso))
+}
+
+/// Formats an [`OffsetDateTime`] with a `time` [format description], e.g.
+/// `"[year]-[month]-[day] [hour]:[minute] [offset_hour sign:mandatory]"`, into a Python string.
+///
+/// A `ValueError` is raised if `fmt` is not a valid format description.
+///
+/// [format description]: https://time-rs.github.io/book/api/format-description.html
+pub fn offset_datetime_format<'py>(
+    py: Python<'py>,
+    datetime: &OffsetDateTime,
+    fmt: &str,
+) -> PyResult<Bound<'py, PyString>> {
+    format_into_py(py, fmt, |items| datetime.format(items))
+}
+
+/// Formats a [`PrimitiveDateTime`] with a `time` format description into a Python string, like
+/// [`offset_datetime_format`].
+///
+/// Besides invalid format descriptions, a `ValueError` is also raised if `fmt` contains offset
+/// components, which a [`PrimitiveDateTime`] doesn't have.
+pub fn primitive_datetime_format<'py>(
+    py: Python<'py>,
+    datetime: PrimitiveDateTime,
+    fmt: &str,
+) -> PyResult<Bound<'py, PyString>> {
+    format_into_py(py, fmt, |items| datetime.format(items))
+}
+
+/// Formats a [`Date`] with a `time` format description into a Python string, like
+/// [`offset_datetime_format`]. Only date components may be used.
+pub fn date_format<'py>(py: Python<'py>, date: Date, fmt: &str) -> PyResult<Bound<'py, PyString>> {
+    format_into_py(py, fmt, |items| date.format(items))
+}
+
+/// Formats a [`Time`] with a `time` format description into a Python string, like
+/// [`offset_datetime_format`]. Only time components may be used.
+pub fn time_format<'py>(py: Python<'py>, time: Time, fmt: &str) -> PyResult<Bound<'py, PyString>> {
+    format_into_py(py, fmt, |items| time.format(items))
+}
+
+/// Parses a Python string with a `time` [format description] into an [`OffsetDateTime`], the
+/// inverse of [`offset_datetime_format`].
+///
+/// A `ValueError` is raised if `fmt` is not a valid format description, or if `s` doesn't match
+/// it or lacks the components needed to build an [`OffsetDateTime`].
+///
+/// [format description]: https://time-rs.github.io/book/api/format-description.html
+pub fn offset_datetime_parse(
+    _py: Python<'_>,
+    s: &Bound<'_, PyString>,
+    fmt: &str,
+) -> PyResult<OffsetDateTime> {
+    parse_from_py(s, fmt, |s, items| OffsetDateTime::parse(s, items))
+}
+
+/// Parses a Python string with a `time` format description into a [`PrimitiveDateTime`], like
+/// [`offset_datetime_parse`].
+pub fn primitive_datetime_parse(
+    _py: Python<'_>,
+    s: &Bound<'_, PyString>,
+    fmt: &str,
+) -> PyResult<PrimitiveDateTime> {
+    parse_from_py(s, fmt, |s, items| PrimitiveDateTime::parse(s, items))
+}
+
+/// Parses a Python string with a `time` format description into a [`Date`], like
+/// [`offset_datetime_parse`].
+pub fn date_parse(_py: Python<'_>, s: &Bound<'_, PyString>, fmt: &str) -> PyResult<Date> {
+    parse_from_py(s, fmt, |s, items| Date::parse(s, items))
+}
+
+/// Parses a Python string with a `time` format description into a [`Time`], like
+/// [`offset_datetime_parse`].
+pub fn time_parse(_py: Python<'_>, s: &Bound<'_, PyString>, fmt: &str) -> PyResult<Time> {
+    parse_from_py(s, fmt, |s, items| Time::parse(s, items))
+}
+
+/// Returns whether daylight saving time is in effect for `tzinfo` at the instant `at`.
+///
+/// A [`UtcOffset`] only captures the total offset from UTC, so this queries Python's
+/// `tzinfo.dst()` for the wall clock time in `tzinfo` corresponding to `at`. A non-zero DST
+/// adjustment maps to `Some(true)`, a zero adjustment to `Some(false)`, and `None` (as returned by
+/// fixed offset timezones such as `datetime.timezone`) to `None`.
+pub fn tzinfo_is_dst(tzinfo: &Bound<'_, PyAny>, at: &OffsetDateTime) -> PyResult<Option<bool>> {
+    let py = tzinfo.py();
+    let local = at
+        .into_pyobject(py)?
+        .call_method1(intern!(py, "astimezone"), (tzinfo,))?;
+    let dst = tzinfo.call_method1(intern!(py, "dst"), (local,))?;
+    if dst.is_none() {
+        return Ok(None);
+    }
+    let dst: Duration = dst.extract()?;
+    Ok(Some(!dst.is_zero()))
+}
+
+/// Checks that `tzinfo` is consistent with itself at the instant `at`, as a sanity check for
+/// custom `tzinfo` implementations before trusting the [`OffsetDateTime`]s extracted with them.
+///
+/// A correct `tzinfo` satisfies `utcoffset() == standard offset + dst()`. The standard offset is
+/// derived as `utcoffset() - dst()` at the wall clock time of `at` in `tzinfo`, where a `None`
+/// from `dst()` counts as no adjustment. `false` is returned if
+/// - `utcoffset()` returns `None`,
+/// - the wall clock time minus `utcoffset()` is not `at` (to microsecond precision), i.e.
+///   `tzinfo.fromutc()` disagrees with `utcoffset()`, or
+/// - half a year before or after `at`, where `dst()` reports no adjustment, `utcoffset()` is not
+///   the derived standard offset. This also flags zones that changed their standard offset in
+///   that time.
+///
+/// Exceptions raised by `tzinfo` are propagated.
+pub fn validate_tzinfo_consistency(
+    tzinfo: &Bound<'_, PyAny>,
+    at: &OffsetDateTime,
+) -> PyResult<bool> {
+    let py = tzinfo.py();
+    let offsets = |at: OffsetDateTime| -> PyResult<(Bound<'_, PyAny>, Option<Duration>, Duration)> {
+        // The default `tzinfo.fromutc()` raises instead of converting if `utcoffset()` returns
+        // `None` for the UTC wall clock time, so check for that before calling `astimezone`
+        let utc = at.to_offset(UtcOffset::UTC);
+        let kwargs = [(intern!(py, "tzinfo"), tzinfo)].into_py_dict(py)?;
+        let utc_wall = PrimitiveDateTime::new(utc.date(), utc.time())
+            .into_pyobject(py)?
+            .call_method(intern!(py, "replace"), (), Some(&kwargs))?;
+        if tzinfo
+            .call_method1(intern!(py, "utcoffset"), (&utc_wall,))?
+            .is_none()
+        {
+            return Ok((utc_wall, None, Duration::ZERO));
+        }
+        let local = at
+            .into_pyobject(py)?
+            .call_method1(intern!(py, "astimezone"), (tzinfo,))?;
+        let offset: Option<Duration> = tzinfo
+            .call_method1(intern!(py, "utcoffset"), (&local,))?
+            .extract()?;
+        let dst: Option<Duration> = tzinfo
+            .call_method1(intern!(py, "dst"), (&local,))?
+            .extract()?;
+        Ok((local, offset, dst.unwrap_or(Duration::ZERO)))
+    };
+
+    let (local, offset, dst) = offsets(*at)?;
+    let offset = match offset {
+        Some(offset) => offset,
+        None => return Ok(false),
+    };
+    let standard = offset - dst;
+
+    let kwargs = [(intern!(py, "tzinfo"), py.None())].into_py_dict(py)?;
+    let wall: PrimitiveDateTime = local
+        .call_method(intern!(py, "replace"), (), Some(&kwargs))?
+        .extract()?;
+    let instant = at
+        .replace_nanosecond(at.nanosecond() / 1000 * 1000)
+        .unwrap();
+    if wall.assume_utc() - offset != instant {
+        return Ok(false);
+    }
+
+    for probe in [Duration::days(-182), Duration::days(182)] {
+        let probe = match at.checked_add(probe) {
+            Some(probe) if PY_YEAR_RANGE.contains(&probe.year()) => probe,
+            _ => continue,
+        };
+        if let (_, Some(probe_offset), probe_dst) = offsets(probe)? {
+            if probe_dst.is_zero() && probe_offset != standard {
+                return Ok(false);
+            }
+        }
+    }
+    Ok(true)
+}
+
+/// Converts an [`OffsetDateTime`] to a Python `time.struct_time` of its wall clock time, like
+/// Python's `datetime.timetuple()`.
+///
+/// The offset alone doesn't tell whether DST is in effect, so `tm_isdst` is `-1` unless a
+/// `tzinfo` is given, in which case it is `1` or `0` as reported by [`tzinfo_is_dst`] (still `-1`
+/// for a `tzinfo` without DST information).
+pub fn offset_datetime_to_struct_time<'py>(
+    py: Python<'py>,
+    dt: &OffsetDateTime,
+    #[cfg(not(Py_LIMITED_API))] tzinfo: Option<&Bound<'py, PyTzInfo>>,
+    #[cfg(Py_LIMITED_API)] tzinfo: Option<&Bound<'py, PyAny>>,
+) -> PyResult<Bound<'py, PyAny>> {
+    check_py_year(dt.year())?;
+    let is_dst = match tzinfo {
+        Some(tzinfo) => tzinfo_is_dst(tzinfo.as_any(), dt)?,
+        None => None,
+    };
+    new_struct_time(py, dt, is_dst, None)
+}
+
+/// The outputs of [`offset_datetime_to_named_outputs`], which all agree on the time zone name.
+#[derive(Debug)]
+pub struct NamedZoneOutputs<'py> {
+    /// The aware Python `datetime.datetime` in the supplied zone.
+    pub datetime: <OffsetDateTime as IntoPyObject<'py>>::Output,
+    /// The zone's name for the instant, as returned by `datetime.tzname()`, e.g. `"BST"`.
+    pub tzname: Option<String>,
+    /// The ISO 8601 string of the wall clock time in the supplied zone. ISO 8601 has no room for
+    /// zone names, so only the offset is included.
+    pub isoformat: Bound<'py, PyString>,
+    /// The `time.struct_time` of the wall clock time in the supplied zone, with `tm_zone` set to
+    /// the zone's name and `tm_gmtoff` to its offset.
+    pub struct_time: Bound<'py, PyAny>,
+}
+
+/// Converts an [`OffsetDateTime`] to the wall clock time in `tzinfo`, e.g. a `zoneinfo.ZoneInfo`
+/// or a `datetime.timezone` with a name, as a Python `datetime`, an ISO 8601 string and a
+/// `time.struct_time`, and reports the zone's name for that instant.
+///
+/// The name is taken from the produced `datetime`'s `tzname()` once, so the outputs can't
+/// disagree on it.
+pub fn offset_datetime_to_named_outputs<'py>(
+    py: Python<'py>,
+    dt: &OffsetDateTime,
+    #[cfg(not(Py_LIMITED_API))] tzinfo: &Bound<'py, PyTzInfo>,
+    #[cfg(Py_LIMITED_API)] tzinfo: &Bound<'py, PyAny>,
+) -> PyResult<NamedZoneOutputs<'py>> {
+    check_py_year(dt.year())?;
+    let datetime = dt
+        .into_pyobject(py)?
+        .call_method1(intern!(py, "astimezone"), (tzinfo,))?;
+    // Moving to the zone's offset in Rust keeps the sub-microsecond precision
+    let local = dt.to_offset(py_timedelta_to_utc_offset(
+        &datetime.call_method0(intern!(py, "utcoffset"))?,
+    )?);
+    let tzname: Option<String> = datetime.call_method0(intern!(py, "tzname"))?.extract()?;
+    let is_dst = tzinfo_is_dst(tzinfo.as_any(), dt)?;
+    let struct_time = new_struct_time(
+        py,
+        &local,
+        is_dst,
+        Some((tzname.as_deref(), local.offset().whole_seconds())),
+    )?;
+    #[cfg(not(Py_LIMITED_API))]
+    let datetime = datetime.downcast_into()?;
+    Ok(NamedZoneOutputs {
+        datetime,
+        isoformat: offset_datetime_to_isoformat(py, &local, false)?,
+        tzname,
+        struct_time,
+    })
+}
+
+/// Builds a `time.struct_time` for the wall clock time of `dt`, optionally with `tm_zone` and
+/// `tm_gmtoff`.
+fn new_struct_time<'py>(
+    py: Python<'py>,
+    dt: &OffsetDateTime,
+    is_dst: Option<bool>,
+    zone: Option<(Option<&str>, i32)>,
+) -> PyResult<Bound<'py, PyAny>> {
+    let DateArgs { year, month, day } = (&dt.date()).into();
+    let (tm_zone, tm_gmtoff) = match zone {
+        Some((tm_zone, tm_gmtoff)) => (tm_zone, Some(tm_gmtoff)),
+        None => (None, None),
+    };
+    let fields = (
+        year,
+        month,
+        day,
+        dt.hour(),
+        dt.minute(),
+        dt.second(),
+        dt.weekday().number_days_from_monday(),
+        dt.ordinal(),
+        is_dst.map_or(-1, i32::from),
+        tm_zone,
+        tm_gmtoff,
+    );
+    py.import(intern!(py, "time"))?
+        .getattr(intern!(py, "struct_time"))?
+        .call1((fields,))
+}
+
 #[allow(deprecated)]
 impl ToPyObject for UtcOffset {
     #[inline]
@@ -565,22 +2428,322 @@ impl<'py> IntoPyObject<'py> for UtcOffset {
     type Error = PyErr;
 
     fn into_pyobject(self, py: Python<'py>) -> Result<Self::Output, Self::Error> {
-        let seconds_offset = self.whole_seconds();
+        match tz_repr_policy() {
+            TzReprPolicy::FixedOffset => {}
+            TzReprPolicy::PreferUtcSingleton => {
+                if self.is_utc() {
+                    return Ok(timezone_utc(py));
+                }
+            }
+            TzReprPolicy::PreferZoneInfoKey => {
+                if let Some(key) = zoneinfo_key(self) {
+                    return zoneinfo_from_key(py, &key);
+                }
+            }
+        }
+
+        fixed_offset_timezone(py, self)
+    }
+}
+
+/// The number of distinct offsets for which [`fixed_offset_timezone`] keeps the `timezone`.
+///
+/// Most data only uses a handful of offsets (often just UTC), so a small cache suffices.
+const TIMEZONE_CACHE_SIZE: usize = 16;
+
+type TimezoneCache = Mutex<Vec<(UtcOffset, Py<<UtcOffset as IntoPyObject<'static>>::Target>)>>;
+
+/// Returns a fixed offset `datetime.timezone` for `offset`, reusing the objects of recently used
+/// offsets.
+fn fixed_offset_timezone(
+    py: Python<'_>,
+    offset: UtcOffset,
+) -> PyResult<<UtcOffset as IntoPyObject<'_>>::Output> {
+    check_py_offset(offset)?;
+    static CACHE: InterpreterOnceCell<TimezoneCache> = InterpreterOnceCell::new();
+    let cache = CACHE.get_or_try_init(py, || {
+        Ok(Mutex::new(Vec::with_capacity(TIMEZONE_CACHE_SIZE)))
+    })?;
+
+    {
+        let mut cache = cache.lock().unwrap();
+        if let Some(index) = cache.iter().position(|(cached, _)| *cached == offset) {
+            // Move to the front so that the least recently used offset is evicted first
+            let entry = cache.remove(index);
+            let tz = entry.1.bind(py).clone();
+            cache.insert(0, entry);
+            return Ok(tz);
+        }
+    }
+
+    // The lock is not held while calling into Python, which may release the GIL
+    let seconds_offset = offset.whole_seconds();
+    #[cfg(not(Py_LIMITED_API))]
+    let tz = {
+        let td = PyDelta::new(py, 0, seconds_offset, 0, true)?;
+        timezone_from_offset(&td)?
+    };
+
+    #[cfg(Py_LIMITED_API)]
+    let tz = {
+        let td = Duration::seconds(seconds_offset.into()).into_pyobject(py)?;
+        DatetimeTypes::try_get(py).and_then(|dt| dt.timezone.bind(py).call1((td,)))?
+    };
+
+    let mut cache = cache.lock().unwrap();
+    if !cache.iter().any(|(cached, _)| *cached == offset) {
+        cache.truncate(TIMEZONE_CACHE_SIZE - 1);
+        cache.insert(0, (offset, tz.clone().unbind()));
+    }
+    Ok(tz)
+}
+
+/// Controls which Python `tzinfo` objects are produced when converting a [`UtcOffset`], and
+/// therefore also an [`OffsetDateTime`], to Python.
+///
+/// The policy is stored per thread and set with [`set_tz_repr_policy`].
+///
+/// A UTC [`OffsetDateTime`] always gets the `datetime.timezone.utc` singleton unless the policy is
+/// [`TzReprPolicy::PreferZoneInfoKey`], so for [`OffsetDateTime`]s the first two policies behave
+/// the same.
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Hash, Default)]
+pub enum TzReprPolicy {
+    /// Produce a fixed offset `datetime.timezone`, except for UTC [`OffsetDateTime`]s, which get
+    /// the `datetime.timezone.utc` singleton.
+    #[default]
+    FixedOffset,
+    /// Produce the `datetime.timezone.utc` singleton for a zero offset, and a fixed offset
+    /// `datetime.timezone` otherwise.
+    PreferUtcSingleton,
+    /// Produce a `zoneinfo.ZoneInfo` for offsets with an IANA key, i.e. `"UTC"` for a zero offset
+    /// and `"Etc/GMT-5"`-style keys for whole hour offsets from -12:00 to +14:00, and a fixed
+    /// offset `datetime.timezone` otherwise. Requires the `zoneinfo` module (Python 3.9+).
+    PreferZoneInfoKey,
+}
+
+thread_local! {
+    static TZ_REPR_POLICY: Cell<TzReprPolicy> = const { Cell::new(TzReprPolicy::FixedOffset) };
+}
+
+/// Sets the [`TzReprPolicy`] used by conversions on the current thread, returning the previous
+/// policy.
+pub fn set_tz_repr_policy(policy: TzReprPolicy) -> TzReprPolicy {
+    TZ_REPR_POLICY.with(|cell| cell.replace(policy))
+}
+
+/// Returns the [`TzReprPolicy`] used by conversions on the current thread.
+pub fn tz_repr_policy() -> TzReprPolicy {
+    TZ_REPR_POLICY.with(Cell::get)
+}
+
+/// The Python classes whose construction can be substituted with [`set_datetime_class`].
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Hash)]
+pub enum DatetimeClass {
+    /// `datetime.date`, produced by the [`Date`] conversion.
+    Date,
+    /// `datetime.time`, produced by the [`Time`] conversion.
+    Time,
+    /// `datetime.datetime`, produced by the [`PrimitiveDateTime`] and [`OffsetDateTime`]
+    /// conversions.
+    DateTime,
+}
+
+impl DatetimeClass {
+    #[cfg_attr(not(Py_LIMITED_API), allow(clippy::unnecessary_wraps))]
+    fn builtin(self, py: Python<'_>) -> PyResult<Bound<'_, PyAny>> {
         #[cfg(not(Py_LIMITED_API))]
         {
-            let td = PyDelta::new(py, 0, seconds_offset, 0, true)?;
-            timezone_from_offset(&td)
+            Ok(match self {
+                DatetimeClass::Date => py.get_type::<PyDate>().into_any(),
+                DatetimeClass::Time => py.get_type::<PyTime>().into_any(),
+                DatetimeClass::DateTime => py.get_type::<PyDateTime>().into_any(),
+            })
         }
 
         #[cfg(Py_LIMITED_API)]
         {
-            let td = Duration::seconds(seconds_offset.into()).into_pyobject(py)?;
-            DatetimeTypes::try_get(py).and_then(|dt| dt.timezone.bind(py).call1((td,)))
+            let types = DatetimeTypes::try_get(py)?;
+            Ok(match self {
+                DatetimeClass::Date => types.date.bind(py).clone(),
+                DatetimeClass::Time => types.time.bind(py).clone(),
+                DatetimeClass::DateTime => types.datetime.bind(py).clone(),
+            })
+        }
+    }
+
+    fn name(self) -> &'static str {
+        match self {
+            DatetimeClass::Date => "date",
+            DatetimeClass::Time => "time",
+            DatetimeClass::DateTime => "datetime",
         }
     }
 }
 
-impl<'py> IntoPyObject<'py> for &UtcOffset {
+/// The classes registered with [`set_datetime_class`], indexed by [`DatetimeClass`].
+static DATETIME_CLASSES: InterpreterOnceCell<Mutex<[Option<Py<PyType>>; 3]>> =
+    InterpreterOnceCell::new();
+
+/// Whether any class is registered in [`DATETIME_CLASSES`], so that conversions can skip the lock
+/// while only the builtin classes are used.
+static DATETIME_CLASS_REGISTERED: AtomicBool = AtomicBool::new(false);
+
+/// Makes conversions construct instances of `cls` instead of the builtin class for `kind`, e.g. a
+/// framework's own `datetime` subclass, returning the previously registered class. Passing `None`
+/// restores the builtin class.
+///
+/// `cls` must be a subclass of the builtin class, and is called with the same arguments, so it
+/// must accept them. Unlike the [`TzReprPolicy`], the registry is shared by all threads, so that a
+/// class registered once, e.g. when a module is imported, is also used by conversions on worker
+/// threads.
+pub fn set_datetime_class(
+    py: Python<'_>,
+    kind: DatetimeClass,
+    cls: Option<&Bound<'_, PyType>>,
+) -> PyResult<Option<Py<PyType>>> {
+    if let Some(cls) = cls {
+        if !cls.is_subclass(&kind.builtin(py)?)? {
+            return Err(PyTypeError::new_err(format!(
+                "{} is not a subclass of datetime.{}",
+                cls,
+                kind.name()
+            )));
+        }
+    }
+    let cls = cls.map(|cls| cls.clone().unbind());
+    let classes = DATETIME_CLASSES.get_or_try_init(py, || Ok(Mutex::new([None, None, None])))?;
+    // A panic while holding the lock can't leave the classes inconsistent
+    let mut classes = classes.lock().unwrap_or_else(PoisonError::into_inner);
+    let previous = std::mem::replace(&mut classes[kind as usize], cls);
+    DATETIME_CLASS_REGISTERED.store(classes.iter().any(Option::is_some), AtomicOrdering::Release);
+    Ok(previous)
+}
+
+/// Calls the class registered for `kind` with `args`, if there is one.
+fn call_registered_class<'py, T, A>(
+    py: Python<'py>,
+    kind: DatetimeClass,
+    args: A,
+) -> PyResult<Option<Bound<'py, T>>>
+where
+    T: PyTypeCheck,
+    A: IntoPyObject<'py, Target = PyTuple>,
+{
+    if !DATETIME_CLASS_REGISTERED.load(AtomicOrdering::Acquire) {
+        return Ok(None);
+    }
+    let cls = match DATETIME_CLASSES.get(py)? {
+        Some(classes) => classes.lock().unwrap_or_else(PoisonError::into_inner)[kind as usize]
+            .as_ref()
+            .map(|cls| cls.clone_ref(py)),
+        None => None,
+    };
+    match cls {
+        Some(cls) => Ok(Some(cls.into_bound(py).call1(args)?.downcast_into()?)),
+        None => Ok(None),
+    }
+}
+
+fn zoneinfo_key(offset: UtcOffset) -> Option<String> {
+    if offset.is_utc() {
+        return Some("UTC".to_owned());
+    }
+    if offset.minutes_past_hour() != 0 || offset.seconds_past_minute() != 0 {
+        return None;
+    }
+    // The signs of the `Etc/GMT` zones are inverted, e.g. `Etc/GMT-5` is UTC+05:00
+    match offset.whole_hours() {
+        hours @ 1..=14 => Some(format!("Etc/GMT-{}", hours)),
+        hours @ -12..=-1 => Some(format!("Etc/GMT+{}", -hours)),
+        _ => None,
+    }
+}
+
+/// Converts an optional [`UtcOffset`] to an optional `tzinfo`, ready to be passed on as the
+/// `tzinfo` argument of e.g. `PyDateTime::new`.
+///
+/// `Option<UtcOffset>` itself also converts to Python, to either a `datetime.timezone` or `None`,
+/// but as a `PyAny` since those have no more specific common type.
+pub fn tzinfo_from_optional_offset(
+    py: Python<'_>,
+    offset: Option<UtcOffset>,
+) -> PyResult<Option<<UtcOffset as IntoPyObject<'_>>::Output>> {
+    offset.map(|offset| offset.into_pyobject(py)).transpose()
+}
+
+/// Returns the `zoneinfo.ZoneInfo` for an IANA time zone key such as `"Europe/London"`.
+///
+/// An unknown key raises a `ValueError` naming it, with Python's `ZoneInfoNotFoundError` as the
+/// cause. Requires the `zoneinfo` module (Python 3.9+).
+pub fn zoneinfo_from_key<'py>(
+    py: Python<'py>,
+    key: &str,
+) -> PyResult<<UtcOffset as IntoPyObject<'py>>::Output> {
+    let zone = zoneinfo_type(py)?.call1((key,)).map_err(|err| {
+        // `ZoneInfoNotFoundError` is a subclass of `KeyError`
+        if err.is_instance_of::<PyKeyError>(py) {
+            let unknown = PyValueError::new_err(format!("unknown time zone {:?}", key));
+            unknown.set_cause(py, Some(err));
+            unknown
+        } else {
+            err
+        }
+    })?;
+    #[cfg(not(Py_LIMITED_API))]
+    let zone = zone.downcast_into()?;
+    Ok(zone)
+}
+
+/// Returns the cached `zoneinfo.ZoneInfo` type.
+fn zoneinfo_type(py: Python<'_>) -> PyResult<&Bound<'_, PyAny>> {
+    let import = || {
+        py.import(intern!(py, "zoneinfo"))?
+            .getattr(intern!(py, "ZoneInfo"))
+            .map(Bound::unbind)
+    };
+    #[cfg(not(Py_LIMITED_API))]
+    let zoneinfo = {
+        static ZONEINFO: InterpreterOnceCell<PyObject> = InterpreterOnceCell::new();
+        ZONEINFO.get_or_try_init(py, import)?
+    };
+    #[cfg(Py_LIMITED_API)]
+    let zoneinfo = DatetimeTypes::try_get(py)?
+        .zoneinfo
+        .get_or_try_init(py, import)?;
+    Ok(zoneinfo.bind(py))
+}
+
+/// Returns the `datetime.timezone` type.
+fn timezone_type(py: Python<'_>) -> PyResult<&Bound<'_, PyAny>> {
+    #[cfg(not(Py_LIMITED_API))]
+    let timezone = {
+        static TIMEZONE: InterpreterOnceCell<PyObject> = InterpreterOnceCell::new();
+        TIMEZONE.get_or_try_init(py, || {
+            py.import(intern!(py, "datetime"))?
+                .getattr(intern!(py, "timezone"))
+                .map(Bound::unbind)
+        })?
+    };
+    #[cfg(Py_LIMITED_API)]
+    let timezone = &DatetimeTypes::try_get(py)?.timezone;
+    Ok(timezone.bind(py))
+}
+
+/// The common ways to attach a time zone to a Python `datetime`, converted to the matching
+/// `tzinfo`.
+///
+/// Unlike the [`UtcOffset`] conversion, this doesn't depend on the [`TzReprPolicy`].
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Hash)]
+pub enum CommonZone {
+    /// The `datetime.timezone.utc` singleton.
+    Utc,
+    /// A fixed offset `datetime.timezone`.
+    FixedOffset(UtcOffset),
+    /// A `zoneinfo.ZoneInfo` for an IANA key such as `"Europe/London"`, see [`zoneinfo_from_key`].
+    Named(&'static str),
+}
+
+impl<'py> IntoPyObject<'py> for CommonZone {
     #[cfg(Py_LIMITED_API)]
     type Target = PyAny;
     #[cfg(not(Py_LIMITED_API))]
@@ -588,13 +2751,44 @@ impl<'py> IntoPyObject<'py> for &UtcOffset {
     type Output = Bound<'py, Self::Target>;
     type Error = PyErr;
 
-    #[inline]
     fn into_pyobject(self, py: Python<'py>) -> Result<Self::Output, Self::Error> {
-        (*self).into_pyobject(py)
+        match self {
+            CommonZone::Utc => Ok(timezone_utc(py)),
+            CommonZone::FixedOffset(offset) => fixed_offset_timezone(py, offset),
+            CommonZone::Named(key) => zoneinfo_from_key(py, key),
+        }
     }
 }
 
-impl FromPyObject<'_> for UtcOffset {
+impl<'py> IntoPyObject<'py> for &CommonZone {
+    #[cfg(Py_LIMITED_API)]
+    type Target = PyAny;
+    #[cfg(not(Py_LIMITED_API))]
+    type Target = PyTzInfo;
+    type Output = Bound<'py, Self::Target>;
+    type Error = PyErr;
+
+    #[inline]
+    fn into_pyobject(self, py: Python<'py>) -> Result<Self::Output, Self::Error> {
+        (*self).into_pyobject(py)
+    }
+}
+
+impl<'py> IntoPyObject<'py> for &UtcOffset {
+    #[cfg(Py_LIMITED_API)]
+    type Target = PyAny;
+    #[cfg(not(Py_LIMITED_API))]
+    type Target = PyTzInfo;
+    type Output = Bound<'py, Self::Target>;
+    type Error = PyErr;
+
+    #[inline]
+    fn into_pyobject(self, py: Python<'py>) -> Result<Self::Output, Self::Error> {
+        (*self).into_pyobject(py)
+    }
+}
+
+impl FromPyObject<'_> for UtcOffset {
     /// Convert python tzinfo to rust [`FixedOffset`].
     ///
     /// Note that the conversion will result in precision lost in microseconds as chrono offset
@@ -617,13 +2811,32 @@ impl FromPyObject<'_> for UtcOffset {
                 ob
             )));
         }
-        let total_seconds: Duration = py_timedelta.extract()?;
-        // This cast is safe since the timedelta is limited to -24 hours and 24 hours.
-        let total_seconds = total_seconds.whole_seconds() as i32;
-        UtcOffset::from_whole_seconds(total_seconds)
-            .or_else(|_| Err(PyValueError::new_err("fixed offset out of bounds")))
-        // .ok_or_else(|| PyValueError::new_err("fixed offset out of bounds"))
+        py_timedelta_to_utc_offset(&py_timedelta)
+    }
+}
+
+fn py_timedelta_to_utc_offset(py_timedelta: &Bound<'_, PyAny>) -> PyResult<UtcOffset> {
+    let total_seconds: Duration = py_timedelta.extract()?;
+    // Python only allows offsets strictly between -24 hours and 24 hours, but a custom tzinfo
+    // can return anything from `utcoffset`.
+    if total_seconds.whole_days() != 0 {
+        return Err(PyValueError::new_err(format!(
+            "offset must be strictly between -24 hours and 24 hours, got {}",
+            py_timedelta.repr()?
+        )));
+    }
+    // Python allows offsets with microseconds, but UtcOffset has whole second resolution.
+    if total_seconds.subsec_nanoseconds() != 0 {
+        return Err(PyValueError::new_err(format!(
+            "offset {} has a fractional second, which UtcOffset cannot represent",
+            py_timedelta.repr()?
+        )));
     }
+    // This cast is safe since the timedelta is limited to -24 hours and 24 hours.
+    let total_seconds = total_seconds.whole_seconds() as i32;
+    UtcOffset::from_whole_seconds(total_seconds)
+        .map_err(|_| PyValueError::new_err("fixed offset out of bounds"))
+    // .ok_or_else(|| PyValueError::new_err("fixed offset out of bounds"))
 }
 
 // #[allow(deprecated)]
@@ -687,6 +2900,124 @@ impl FromPyObject<'_> for UtcOffset {
 //     }
 // }
 
+/// Types whose conversion to Python is lossless only up to Python's microsecond resolution.
+///
+/// Used by [`assert_roundtrip_stable`] to compute the value expected back from Python.
+#[cfg(feature = "testing")]
+pub trait PyResolution: Sized {
+    /// Returns `self` with everything Python's `datetime` cannot represent truncated, the same way
+    /// the conversion to Python does.
+    fn truncated_to_py_resolution(&self) -> Self;
+}
+
+#[cfg(feature = "testing")]
+impl PyResolution for Duration {
+    fn truncated_to_py_resolution(&self) -> Self {
+        // `whole_microseconds` truncates towards zero, like the conversion to `timedelta`
+        Duration::seconds(self.whole_seconds())
+            + Duration::microseconds(self.subsec_microseconds().into())
+    }
+}
+
+#[cfg(feature = "testing")]
+impl PyResolution for Date {
+    fn truncated_to_py_resolution(&self) -> Self {
+        *self
+    }
+}
+
+#[cfg(feature = "testing")]
+impl PyResolution for Time {
+    fn truncated_to_py_resolution(&self) -> Self {
+        // `time` has no leap seconds, so only the sub-microsecond part can be lost
+        self.replace_nanosecond(self.nanosecond() / 1000 * 1000)
+            .expect("truncated nanosecond is in range")
+    }
+}
+
+#[cfg(feature = "testing")]
+impl PyResolution for PrimitiveDateTime {
+    fn truncated_to_py_resolution(&self) -> Self {
+        self.replace_time(self.time().truncated_to_py_resolution())
+    }
+}
+
+#[cfg(feature = "testing")]
+impl PyResolution for OffsetDateTime {
+    fn truncated_to_py_resolution(&self) -> Self {
+        self.replace_time(self.time().truncated_to_py_resolution())
+    }
+}
+
+/// Asserts that converting `value` to Python and back yields `value` again, up to Python's
+/// microsecond resolution (see [`PyResolution`]).
+///
+/// Intended for the tests of downstream crates relying on the round trip of [`Duration`],
+/// [`Date`], [`Time`], [`PrimitiveDateTime`] or [`OffsetDateTime`].
+///
+/// # Panics
+///
+/// Panics if either conversion fails or the value changed.
+#[cfg(feature = "testing")]
+pub fn assert_roundtrip_stable<T>(py: Python<'_>, value: T)
+where
+    T: PyResolution + Copy + PartialEq + std::fmt::Debug + for<'py> FromPyObject<'py>,
+    for<'py> T: IntoPyObject<'py, Error = PyErr>,
+{
+    use crate::BoundObject;
+
+    let obj = value
+        .into_pyobject(py)
+        .unwrap_or_else(|err| panic!("failed to convert {:?} to Python: {}", value, err))
+        .into_any()
+        .into_bound();
+    let roundtripped: T = obj
+        .extract()
+        .unwrap_or_else(|err| panic!("failed to extract {:?} from {}: {}", value, obj, err));
+    assert_eq!(
+        roundtripped,
+        value.truncated_to_py_resolution(),
+        "{:?} did not round trip through {}",
+        value,
+        obj
+    );
+}
+
+/// The range of years supported by Python's `datetime` module.
+const PY_YEAR_RANGE: std::ops::RangeInclusive<i32> = 1..=9999;
+
+fn check_py_year(year: i32) -> PyResult<()> {
+    if year < *PY_YEAR_RANGE.start() {
+        Err(PyValueError::new_err(format!(
+            "year {} is out of range for Python's datetime: year must be positive, \
+             as Python has no year 0 or years before the common era",
+            year
+        )))
+    } else if year > *PY_YEAR_RANGE.end() {
+        Err(PyValueError::new_err(format!(
+            "year {} is out of range for Python's datetime: year must be at most {} (MAXYEAR)",
+            year,
+            PY_YEAR_RANGE.end()
+        )))
+    } else {
+        Ok(())
+    }
+}
+
+/// `UtcOffset` allows offsets up to ±25:59:59, but Python's `timezone` requires them to be
+/// strictly within ±24 hours.
+fn check_py_offset(offset: UtcOffset) -> PyResult<()> {
+    if offset.whole_seconds().unsigned_abs() >= 24 * 3600 {
+        Err(PyValueError::new_err(format!(
+            "offset {} is out of range for Python's timezone: offset must be strictly between \
+             -24:00 and +24:00",
+            offset
+        )))
+    } else {
+        Ok(())
+    }
+}
+
 struct DateArgs {
     year: i32,
     month: u8,
@@ -703,6 +3034,34 @@ impl From<&Date> for DateArgs {
     }
 }
 
+/// The components of a `timedelta`, normalized like Python does: only the days are negative for a
+/// negative duration, with 0 <= secs < 86400 and 0 <= micros < 1000000.
+struct DeltaArgs {
+    days: i128,
+    secs: i32,
+    micros: i32,
+}
+
+impl From<&Duration> for DeltaArgs {
+    fn from(value: &Duration) -> Self {
+        let (total_micros, _) = truncate_nanos_to_micros(value.whole_nanoseconds());
+        Self::from_micros(total_micros)
+    }
+}
+
+impl DeltaArgs {
+    fn from_micros(total_micros: i128) -> Self {
+        const MICROS_PER_DAY: i128 = 86_400 * 1_000_000;
+        let micros_of_day = total_micros.rem_euclid(MICROS_PER_DAY);
+        Self {
+            days: total_micros.div_euclid(MICROS_PER_DAY),
+            // These casts are safe since the values are bounded by the above
+            secs: (micros_of_day / 1_000_000) as i32,
+            micros: (micros_of_day % 1_000_000) as i32,
+        }
+    }
+}
+
 struct TimeArgs {
     hour: u8,
     min: u8,
@@ -716,7 +3075,9 @@ impl From<&Time> for TimeArgs {
         let ns = value.nanosecond();
         let checked_sub = ns.checked_sub(1_000_000_000);
         let truncated_leap_second = checked_sub.is_some();
-        let micro = checked_sub.unwrap_or(ns) / 1000;
+        let (micro, _) = truncate_nanos_to_micros(checked_sub.unwrap_or(ns).into());
+        // This cast is safe since there are less than a million microseconds in a second
+        let micro = micro as u32;
         Self {
             hour: value.hour() as u8,
             min: value.minute() as u8,
@@ -727,6 +3088,72 @@ impl From<&Time> for TimeArgs {
     }
 }
 
+/// Builds the Python `time` for `time`, returning whether a leap second was truncated.
+fn time_to_py_time<'py>(
+    py: Python<'py>,
+    time: &Time,
+) -> PyResult<(<Time as IntoPyObject<'py>>::Output, bool)> {
+    let TimeArgs {
+        hour,
+        min,
+        sec,
+        micro,
+        truncated_leap_second,
+    } = time.into();
+
+    if let Some(time) = call_registered_class(py, DatetimeClass::Time, (hour, min, sec, micro))? {
+        return Ok((time, truncated_leap_second));
+    }
+
+    #[cfg(not(Py_LIMITED_API))]
+    let time = PyTime::new(py, hour, min, sec, micro, None)?;
+
+    #[cfg(Py_LIMITED_API)]
+    let time = DatetimeTypes::try_get(py)
+        .and_then(|dt| dt.time.bind(py).call1((hour, min, sec, micro)))?;
+
+    Ok((time, truncated_leap_second))
+}
+
+/// Builds the Python `timedelta` for the normalized components of a [`Duration`].
+fn delta_args_to_py_delta(
+    py: Python<'_>,
+    args: DeltaArgs,
+) -> PyResult<<Duration as IntoPyObject<'_>>::Output> {
+    let DeltaArgs { days, secs, micros } = args;
+
+    #[cfg(not(Py_LIMITED_API))]
+    {
+        // We do not need to check the days i128 to i32 cast from rust because
+        // python will raise an OverflowError for anything outside of +/-999999999 days.
+        // The components are already normalized, so Python doesn't need to do it again.
+        PyDelta::new(py, days.try_into().unwrap_or(i32::MAX), secs, micros, false)
+    }
+
+    #[cfg(Py_LIMITED_API)]
+    {
+        DatetimeTypes::try_get(py).and_then(|dt| {
+            dt.timedelta
+                .bind(py)
+                .call1((i64::try_from(days).unwrap_or(i64::MAX), secs, micros))
+        })
+    }
+}
+
+/// Whether `time` has a part below Python's microsecond resolution.
+fn has_sub_microseconds(time: &Time) -> bool {
+    truncate_nanos_to_micros(time.nanosecond().into()).1
+}
+
+/// Converts nanoseconds to Python's microsecond resolution by truncating towards zero, returning
+/// whether a non-zero sub-microsecond remainder was dropped.
+///
+/// This is the single place deciding how sub-microsecond precision is lost, shared by the
+/// [`Duration`] and [`Time`] conversions.
+fn truncate_nanos_to_micros(nanos: i128) -> (i128, bool) {
+    (nanos / 1000, nanos % 1000 != 0)
+}
+
 fn primitive_datetime_to_py_datetime(
     py: Python<'_>,
     primitive_date_time: &PrimitiveDateTime,
@@ -756,6 +3183,140 @@ fn primitive_datetime_to_py_datetime(
     datetime.into()
 }
 
+/// Builds the Python `datetime` for `dt`, returning whether a leap second was truncated so that
+/// callers can decide how to warn about it.
+///
+/// A UTC datetime always gets the `datetime.timezone.utc` singleton, so that Python code can
+/// check `dt.tzinfo is timezone.utc`, unless a `zoneinfo.ZoneInfo` was asked for with
+/// [`TzReprPolicy::PreferZoneInfoKey`].
+fn offset_datetime_to_py_datetime<'py>(
+    py: Python<'py>,
+    dt: &OffsetDateTime,
+) -> PyResult<(<OffsetDateTime as IntoPyObject<'py>>::Output, bool)> {
+    let tz = if dt.offset().is_utc() && tz_repr_policy() != TzReprPolicy::PreferZoneInfoKey {
+        timezone_utc(py)
+    } else {
+        dt.offset().into_pyobject(py)?
+    };
+    offset_datetime_to_py_datetime_with_tz(py, dt, &tz)
+}
+
+/// Like [`offset_datetime_to_py_datetime`], but with an explicit `tzinfo` which must have the
+/// same offset as `dt`.
+fn offset_datetime_to_py_datetime_with_tz<'py>(
+    py: Python<'py>,
+    dt: &OffsetDateTime,
+    tz: &<UtcOffset as IntoPyObject<'py>>::Output,
+) -> PyResult<(<OffsetDateTime as IntoPyObject<'py>>::Output, bool)> {
+    let DateArgs { year, month, day } = (&dt.date()).into();
+    let TimeArgs {
+        hour,
+        min,
+        sec,
+        micro,
+        truncated_leap_second,
+    } = (&dt.time()).into();
+
+    if let Some(datetime) = call_registered_class(
+        py,
+        DatetimeClass::DateTime,
+        (year, month, day, hour, min, sec, micro, tz),
+    )? {
+        return Ok((datetime, truncated_leap_second));
+    }
+
+    #[cfg(not(Py_LIMITED_API))]
+    let datetime = PyDateTime::new(py, year, month, day, hour, min, sec, micro, Some(tz))?;
+
+    #[cfg(Py_LIMITED_API)]
+    let datetime = DatetimeTypes::try_get(py).and_then(|dt| {
+        dt.datetime
+            .bind(py)
+            .call1((year, month, day, hour, min, sec, micro, tz))
+    })?;
+
+    Ok((datetime, truncated_leap_second))
+}
+
+/// Parses the format description `fmt` and formats a value with it.
+fn format_into_py<'py>(
+    py: Python<'py>,
+    fmt: &str,
+    format: impl FnOnce(&[BorrowedFormatItem<'_>]) -> Result<String, time::error::Format>,
+) -> PyResult<Bound<'py, PyString>> {
+    let items = parse_format_description(fmt)?;
+    let formatted = format(&items)
+        .map_err(|err| PyValueError::new_err(format!("cannot format with {:?}: {}", fmt, err)))?;
+    Ok(PyString::new(py, &formatted))
+}
+
+/// Parses the format description `fmt` and parses the string `s` with it.
+fn parse_from_py<T>(
+    s: &Bound<'_, PyString>,
+    fmt: &str,
+    parse: impl FnOnce(&str, &[BorrowedFormatItem<'_>]) -> Result<T, time::error::Parse>,
+) -> PyResult<T> {
+    let items = parse_format_description(fmt)?;
+    let s = s.to_cow()?;
+    parse(&s, &items).map_err(|err| {
+        PyValueError::new_err(format!("cannot parse {:?} with {:?}: {}", s, fmt, err))
+    })
+}
+
+// `parse_borrowed` is not available in the oldest `time` version we support.
+#[allow(deprecated)]
+fn parse_format_description(fmt: &str) -> PyResult<Vec<BorrowedFormatItem<'_>>> {
+    time::format_description::parse(fmt).map_err(|err| {
+        PyValueError::new_err(format!("invalid format description {:?}: {}", fmt, err))
+    })
+}
+
+fn write_iso_date(out: &mut String, date: &Date) {
+    let DateArgs { year, month, day } = date.into();
+    let _ = write!(out, "{:04}-{:02}-{:02}", year, month, day);
+}
+
+fn write_iso_time(out: &mut String, time: &Time, timespec: TimeSpec) {
+    let TimeArgs {
+        hour,
+        min,
+        sec,
+        micro,
+        ..
+    } = time.into();
+    let timespec = match timespec {
+        TimeSpec::Auto if micro == 0 => TimeSpec::Seconds,
+        TimeSpec::Auto => TimeSpec::Microseconds,
+        timespec => timespec,
+    };
+    let _ = match timespec {
+        TimeSpec::Hours => write!(out, "{:02}", hour),
+        TimeSpec::Minutes => write!(out, "{:02}:{:02}", hour, min),
+        TimeSpec::Seconds => write!(out, "{:02}:{:02}:{:02}", hour, min, sec),
+        TimeSpec::Milliseconds => {
+            write!(out, "{:02}:{:02}:{:02}.{:03}", hour, min, sec, micro / 1000)
+        }
+        TimeSpec::Microseconds | TimeSpec::Auto => {
+            write!(out, "{:02}:{:02}:{:02}.{:06}", hour, min, sec, micro)
+        }
+    };
+}
+
+fn write_iso_offset(out: &mut String, offset: UtcOffset) {
+    let sign = if offset.is_negative() { '-' } else { '+' };
+    let (hours, minutes, seconds) = offset.as_hms();
+    let _ = write!(
+        out,
+        "{}{:02}:{:02}",
+        sign,
+        hours.unsigned_abs(),
+        minutes.unsigned_abs()
+    );
+    if seconds != 0 {
+        let _ = write!(out, ":{:02}", seconds.unsigned_abs());
+    }
+}
+
 fn warn_truncated_leap_second(obj: &Bound<'_, PyAny>) {
     let py = obj.py();
     if let Err(e) = PyErr::warn(
@@ -768,6 +3329,20 @@ fn warn_truncated_leap_second(obj: &Bound<'_, PyAny>) {
     };
 }
 
+/// Reads a [`Date`] from the `year`, `month` and `day` attributes of a date-like object which is
+/// not necessarily a `datetime.date`.
+fn date_from_accessors(ob: &Bound<'_, PyAny>) -> PyResult<Date> {
+    let py = ob.py();
+    let month = Month::try_from(ob.getattr(intern!(py, "month"))?.extract::<u8>()?)
+        .map_err(|_| PyValueError::new_err("invalid month"))?;
+    Date::from_calendar_date(
+        ob.getattr(intern!(py, "year"))?.extract()?,
+        month,
+        ob.getattr(intern!(py, "day"))?.extract()?,
+    )
+    .map_err(|_| PyValueError::new_err("invalid or out-of-range date"))
+}
+
 #[cfg(not(Py_LIMITED_API))]
 fn py_date_to_naive_date(py_date: &impl PyDateAccess) -> PyResult<Date> {
     Date::from_calendar_date(
@@ -775,11 +3350,10 @@ fn py_date_to_naive_date(py_date: &impl PyDateAccess) -> PyResult<Date> {
         py_date
             .get_month()
             .try_into()
-            .or_else(|_| Err(PyValueError::new_err("invalid month")))?,
-        py_date.get_day().into(),
+            .map_err(|_| PyValueError::new_err("invalid month"))?,
+        py_date.get_day(),
     )
-    .or_else(|_| Err(PyValueError::new_err("invalid or out-of-range date")))
-    // .ok_or_else(|| PyValueError::new_err("invalid or out-of-range date"))
+    .map_err(|_| PyValueError::new_err("invalid or out-of-range date"))
 }
 
 #[cfg(Py_LIMITED_API)]
@@ -814,14 +3388,54 @@ fn py_time_to_naive_time(py_time: &Bound<'_, PyAny>) -> PyResult<Time> {
         py_time
             .getattr(intern!(py_time.py(), "second"))?
             .extract()?,
-        py_time
-            .getattr(intern!(py_time.py(), "microsecond"))?
-            .extract()?,
+        extract_microsecond(py_time)?,
     )
     .or_else(|_| Err(PyValueError::new_err("invalid or out-of-range time")))
     // .ok_or_else(|| PyValueError::new_err("invalid or out-of-range time"))
 }
 
+/// Reads the `microsecond` attribute of a `time` or `datetime`-like object, which isn't guaranteed
+/// to be in range when it doesn't come from the stdlib's C implementation.
+fn extract_microsecond(ob: &Bound<'_, PyAny>) -> PyResult<u32> {
+    let microsecond: u32 = ob.getattr(intern!(ob.py(), "microsecond"))?.extract()?;
+    if microsecond >= 1_000_000 {
+        return Err(PyValueError::new_err(format!(
+            "microsecond must be in 0..=999999, got {}",
+            microsecond
+        )));
+    }
+    Ok(microsecond)
+}
+
+/// Adds the sub-microsecond `nanosecond` attribute of `datetime` and `time` subclasses such as
+/// pandas' `Timestamp` to `time`, which was read with microsecond precision.
+///
+/// Plain `datetime.datetime` and `datetime.time` objects have no such attribute, so they skip the
+/// lookup entirely.
+fn with_extra_nanoseconds(ob: &Bound<'_, PyAny>, time: Time) -> PyResult<Time> {
+    let py = ob.py();
+    #[cfg(not(Py_LIMITED_API))]
+    let is_stdlib = ob.is_exact_instance_of::<PyDateTime>() || ob.is_exact_instance_of::<PyTime>();
+    #[cfg(Py_LIMITED_API)]
+    let is_stdlib = {
+        let types = DatetimeTypes::try_get(py)?;
+        let ty = ob.get_type();
+        ty.is(types.datetime.bind(py)) || ty.is(types.time.bind(py))
+    };
+    if is_stdlib || !ob.hasattr(intern!(py, "nanosecond"))? {
+        return Ok(time);
+    }
+    let nanosecond: u32 = ob.getattr(intern!(py, "nanosecond"))?.extract()?;
+    if nanosecond >= 1000 {
+        return Err(PyValueError::new_err(format!(
+            "nanosecond must be in 0..=999, got {}",
+            nanosecond
+        )));
+    }
+    time.replace_nanosecond(time.nanosecond() + nanosecond)
+        .map_err(|_| PyValueError::new_err("invalid or out-of-range time"))
+}
+
 #[cfg(Py_LIMITED_API)]
 fn check_type(value: &Bound<'_, PyAny>, t: &PyObject, type_name: &'static str) -> PyResult<()> {
     if !value.is_instance(t.bind(value.py()))? {
@@ -830,6 +3444,8 @@ fn check_type(value: &Bound<'_, PyAny>, t: &PyObject, type_name: &'static str) -
     Ok(())
 }
 
+/// The `datetime` types used by the limited API conversions, cached for the interpreter which
+/// loaded them, see [`InterpreterOnceCell`].
 #[cfg(Py_LIMITED_API)]
 struct DatetimeTypes {
     date: PyObject,
@@ -839,6 +3455,8 @@ struct DatetimeTypes {
     timezone: PyObject,
     timezone_utc: PyObject,
     tzinfo: PyObject,
+    /// `zoneinfo.ZoneInfo`, loaded on first use since it requires Python 3.9.
+    zoneinfo: GILOnceCell<PyObject>,
 }
 
 #[cfg(Py_LIMITED_API)]
@@ -848,11 +3466,11 @@ impl DatetimeTypes {
     }
 
     fn try_get(py: Python<'_>) -> PyResult<&Self> {
-        static TYPES: GILOnceCell<DatetimeTypes> = GILOnceCell::new();
+        static TYPES: InterpreterOnceCell<DatetimeTypes> = InterpreterOnceCell::new();
         TYPES.get_or_try_init(py, || {
             let datetime = py.import("datetime")?;
             let timezone = datetime.getattr("timezone")?;
-            Ok::<_, PyErr>(Self {
+            Ok(Self {
                 date: datetime.getattr("date")?.into(),
                 datetime: datetime.getattr("datetime")?.into(),
                 time: datetime.getattr("time")?.into(),
@@ -860,11 +3478,93 @@ impl DatetimeTypes {
                 timezone_utc: timezone.getattr("utc")?.into(),
                 timezone: timezone.into(),
                 tzinfo: datetime.getattr("tzinfo")?.into(),
+                zoneinfo: GILOnceCell::new(),
             })
         })
     }
 }
 
+/// A [`GILOnceCell`] for data holding Python objects, which may only be used from the interpreter
+/// that initialized it.
+///
+/// Each (sub-)interpreter has its own modules and types, so cached objects must never be handed
+/// to another interpreter. Like PyO3 modules themselves, the caches of the conversions don't
+/// support sub-interpreters: using one from a second interpreter raises an `ImportError` rather
+/// than silently mixing objects between interpreters. The check is skipped where the interpreter
+/// can't be identified, see [`current_interpreter_id`].
+struct InterpreterOnceCell<T> {
+    cell: GILOnceCell<(Option<i64>, T)>,
+}
+
+impl<T> InterpreterOnceCell<T> {
+    const fn new() -> Self {
+        Self {
+            cell: GILOnceCell::new(),
+        }
+    }
+
+    fn get_or_try_init<F>(&self, py: Python<'_>, f: F) -> PyResult<&T>
+    where
+        F: FnOnce() -> PyResult<T>,
+    {
+        let (interpreter, value) = self
+            .cell
+            .get_or_try_init(py, || Ok::<_, PyErr>((current_interpreter_id(py)?, f()?)))?;
+        check_interpreter(py, *interpreter)?;
+        Ok(value)
+    }
+
+    /// Returns the cached value, or `None` if the cell hasn't been initialized yet.
+    fn get(&self, py: Python<'_>) -> PyResult<Option<&T>> {
+        match self.cell.get(py) {
+            Some((interpreter, value)) => {
+                check_interpreter(py, *interpreter)?;
+                Ok(Some(value))
+            }
+            None => Ok(None),
+        }
+    }
+}
+
+fn check_interpreter(py: Python<'_>, initialized: Option<i64>) -> PyResult<()> {
+    if let (Some(initialized), Some(current)) = (initialized, current_interpreter_id(py)?) {
+        if initialized != current {
+            return Err(PyImportError::new_err(
+                "time conversions do not support subinterpreters, see https://github.com/PyO3/pyo3/issues/576",
+            ));
+        }
+    }
+    Ok(())
+}
+
+/// Returns the ID of the current interpreter, or `None` where it cannot be determined.
+///
+/// PyPy and GraalPy have no sub-interpreters. CPython only provides the ID from 3.9 on, and the
+/// stable ABI's python3.dll on Windows only from 3.10 on. Before that, PyO3 modules can only be
+/// initialized once per process, but an embedding application could still create
+/// sub-interpreters, for which the caches are not guarded.
+fn current_interpreter_id(py: Python<'_>) -> PyResult<Option<i64>> {
+    #[cfg(all(
+        Py_3_9,
+        not(any(PyPy, GraalPy)),
+        not(all(windows, Py_LIMITED_API, not(Py_3_10)))
+    ))]
+    {
+        let id = unsafe { ffi::PyInterpreterState_GetID(ffi::PyInterpreterState_Get()) };
+        crate::err::error_on_minusone(py, id)?;
+        Ok(Some(id))
+    }
+    #[cfg(not(all(
+        Py_3_9,
+        not(any(PyPy, GraalPy)),
+        not(all(windows, Py_LIMITED_API, not(Py_3_10)))
+    )))]
+    {
+        let _ = py;
+        Ok(None)
+    }
+}
+
 #[cfg(Py_LIMITED_API)]
 fn timezone_utc(py: Python<'_>) -> Bound<'_, PyAny> {
     DatetimeTypes::get(py).timezone_utc.bind(py).clone()
@@ -873,7 +3573,9 @@ fn timezone_utc(py: Python<'_>) -> Bound<'_, PyAny> {
 #[cfg(test)]
 mod tests_time {
     use super::*;
-    use crate::{types::PyTuple, BoundObject};
+    use crate::types::list::PyListMethods;
+    use crate::types::IntoPyDict;
+    use crate::BoundObject;
     use std::{cmp::Ordering, panic};
 
     #[test]
@@ -884,7 +3586,6 @@ mod tests_time {
     fn test_zoneinfo_is_not_fixed_offset() {
         use crate::ffi;
         use crate::types::any::PyAnyMethods;
-        use crate::types::dict::PyDictMethods;
 
         Python::with_gil(|py| {
             let locals = crate::types::PyDict::new(py);
@@ -903,6 +3604,161 @@ mod tests_time {
         });
     }
 
+    #[test]
+    fn test_tzinfo_is_dst_fixed_offset() {
+        Python::with_gil(|py| {
+            let at = OffsetDateTime::UNIX_EPOCH;
+            assert_eq!(tzinfo_is_dst(&python_utc(py), &at).unwrap(), None);
+            let tz = UtcOffset::from_hms(2, 0, 0)
+                .unwrap()
+                .into_pyobject(py)
+                .unwrap();
+            assert_eq!(tzinfo_is_dst(&tz, &at).unwrap(), None);
+        })
+    }
+
+    #[test]
+    // Only Python>=3.9 has the zoneinfo package
+    // We skip the test on windows too since we'd need to install
+    // tzdata there to make this work.
+    #[cfg(all(Py_3_9, not(target_os = "windows")))]
+    fn test_tzinfo_is_dst_zoneinfo() {
+        Python::with_gil(|py| {
+            let zi = py
+                .import("zoneinfo")
+                .unwrap()
+                .getattr("ZoneInfo")
+                .unwrap()
+                .call1(("Europe/London",))
+                .unwrap();
+            let summer = Date::from_calendar_date(2024, Month::July, 1)
+                .unwrap()
+                .midnight()
+                .assume_utc();
+            let winter = Date::from_calendar_date(2024, Month::January, 1)
+                .unwrap()
+                .midnight()
+                .assume_utc();
+            assert_eq!(tzinfo_is_dst(&zi, &summer).unwrap(), Some(true));
+            assert_eq!(tzinfo_is_dst(&zi, &winter).unwrap(), Some(false));
+        })
+    }
+
+    #[test]
+    #[cfg(feature = "macros")]
+    fn test_offset_datetime_iterator() {
+        Python::with_gil(|py| {
+            let start = OffsetDateTime::UNIX_EPOCH;
+            let iter = crate::Py::new(
+                py,
+                OffsetDateTimeIterator::new((0..3).map(move |i| start + Duration::days(i))),
+            )
+            .unwrap();
+            let expected = PyList::new(
+                py,
+                [
+                    new_py_datetime_ob(py, "datetime", (1970, 1, 1, 0, 0, 0, 0, python_utc(py))),
+                    new_py_datetime_ob(py, "datetime", (1970, 1, 2, 0, 0, 0, 0, python_utc(py))),
+                    new_py_datetime_ob(py, "datetime", (1970, 1, 3, 0, 0, 0, 0, python_utc(py))),
+                ],
+            )
+            .unwrap();
+            crate::py_run!(
+                py,
+                iter expected,
+                r#"
+                    assert iter is iter.__iter__()
+                    assert list(iter) == expected
+                    assert list(iter) == []
+                "#
+            );
+        })
+    }
+
+    #[test]
+    #[cfg(all(feature = "macros", not(Py_GIL_DISABLED)))]
+    fn test_offset_datetime_iterator_warns_leap_second_once() {
+        Python::with_gil(|py| {
+            // `Time` can't hold a leap second, so simulate several truncated conversions
+            let mut iter = OffsetDateTimeIterator::new(std::iter::empty());
+            let datetime = OffsetDateTime::UNIX_EPOCH.into_pyobject(py).unwrap();
+            assert_warnings!(
+                py,
+                for _ in 0..3 {
+                    iter.warn_truncated_leap_second_once(datetime.as_any());
+                },
+                [(
+                    PyUserWarning,
+                    "ignored leap-second, `datetime` does not support leap-seconds"
+                )]
+            );
+
+            // Each iterator warns once
+            let mut iter = OffsetDateTimeIterator::new(std::iter::empty());
+            assert_warnings!(
+                py,
+                iter.warn_truncated_leap_second_once(datetime.as_any()),
+                [(
+                    PyUserWarning,
+                    "ignored leap-second, `datetime` does not support leap-seconds"
+                )]
+            );
+        })
+    }
+
+    #[test]
+    fn test_fold_ignored_for_fixed_offset() {
+        Python::with_gil(|py| {
+            let tz = UtcOffset::from_hms(1, 0, 0)
+                .unwrap()
+                .into_pyobject(py)
+                .unwrap();
+            let py_datetime = new_py_datetime_ob(py, "datetime", (2021, 11, 7, 1, 30, 0, 0, tz));
+            let folded = py_datetime
+                .call_method(
+                    "replace",
+                    (),
+                    Some(&[("fold", 1)].into_py_dict(py).unwrap()),
+                )
+                .unwrap();
+            let dt: OffsetDateTime = py_datetime.extract().unwrap();
+            let folded_dt: OffsetDateTime = folded.extract().unwrap();
+            assert_eq!(dt, folded_dt);
+            assert_eq!(folded_dt.offset(), UtcOffset::from_hms(1, 0, 0).unwrap());
+        })
+    }
+
+    #[test]
+    // Only Python>=3.9 has the zoneinfo package
+    // We skip the test on windows too since we'd need to install
+    // tzdata there to make this work.
+    #[cfg(all(Py_3_9, not(target_os = "windows")))]
+    fn test_fold_consulted_for_zoneinfo() {
+        Python::with_gil(|py| {
+            let zi = py
+                .import("zoneinfo")
+                .unwrap()
+                .getattr("ZoneInfo")
+                .unwrap()
+                .call1(("America/New_York",))
+                .unwrap();
+            // 01:30 happens twice on this day, first in EDT (fold=0) then in EST (fold=1)
+            let py_datetime = new_py_datetime_ob(py, "datetime", (2021, 11, 7, 1, 30, 0, 0, zi));
+            let folded = py_datetime
+                .call_method(
+                    "replace",
+                    (),
+                    Some(&[("fold", 1)].into_py_dict(py).unwrap()),
+                )
+                .unwrap();
+            let dt: OffsetDateTime = py_datetime.extract().unwrap();
+            let folded_dt: OffsetDateTime = folded.extract().unwrap();
+            assert_eq!(dt.offset(), UtcOffset::from_hms(-4, 0, 0).unwrap());
+            assert_eq!(folded_dt.offset(), UtcOffset::from_hms(-5, 0, 0).unwrap());
+            assert_eq!(folded_dt - dt, Duration::hours(1));
+        })
+    }
+
     #[test]
     fn test_timezone_aware_to_naive_fails() {
         // Test that if a user tries to convert a python's timezone aware datetime into a naive
@@ -1019,6 +3875,36 @@ mod tests_time {
         });
     }
 
+    #[test]
+    fn test_pyo3_timedelta_into_pyobject_sub_second() {
+        Python::with_gil(|py| {
+            let check = |delta: Duration, py_micros: i64| {
+                let delta = delta.into_pyobject(py).unwrap();
+                let py_delta = new_py_datetime_ob(py, "timedelta", (0, 0, py_micros));
+                assert!(delta.eq(&py_delta).unwrap(), "{} != {}", delta, py_delta);
+            };
+
+            check(Duration::microseconds(1), 1);
+            check(Duration::microseconds(-1), -1);
+            check(Duration::microseconds(999_999), 999_999);
+            check(Duration::microseconds(-999_999), -999_999);
+            // Sub-microsecond parts are truncated towards zero
+            check(Duration::nanoseconds(500), 0);
+            check(Duration::nanoseconds(-500), 0);
+            check(Duration::nanoseconds(1500), 1);
+            check(Duration::nanoseconds(1999), 1);
+            check(Duration::nanoseconds(-1500), -1);
+            check(
+                Duration::seconds(1) + Duration::nanoseconds(1500),
+                1_000_001,
+            );
+            check(
+                Duration::seconds(-1) - Duration::nanoseconds(1500),
+                -1_000_001,
+            );
+        });
+    }
+
     #[test]
     fn test_pyo3_timedelta_frompyobject() {
         // Utility function used to check different Durations.
@@ -1123,98 +4009,120 @@ mod tests_time {
         eq_ymd("max date", 9999, 12, 31);
     }
 
-    // #[test]
-    // fn test_pyo3_datetime_into_pyobject_utc() {
-    //     Python::with_gil(|py| {
-    //         let check_utc =
-    //             |name: &'static str, year, month, day, hour, minute, second, ms, py_ms| {
-    //                 let month = Month::try_from(month).unwrap();
-    //                 let datetime = Date::from_calendar_date(year, month, day)
-    //                     .unwrap()
-    //                     .with_hms_micro(hour, minute, second, ms)
-    //                     .unwrap()
-    //                     .assume_utc();
-    //                 let datetime = datetime.into_pyobject(py).unwrap();
-    //                 let py_datetime = new_py_datetime_ob(
-    //                     py,
-    //                     "datetime",
-    //                     (
-    //                         year,
-    //                         month as u8,
-    //                         day,
-    //                         hour,
-    //                         minute,
-    //                         second,
-    //                         py_ms,
-    //                         python_utc(py),
-    //                     ),
-    //                 );
-    //                 assert_eq!(
-    //                     datetime.compare(&py_datetime).unwrap(),
-    //                     Ordering::Equal,
-    //                     "{}: {} != {}",
-    //                     name,
-    //                     datetime,
-    //                     py_datetime
-    //                 );
-    //             };
-
-    //         check_utc("regular", 2014, 5, 6, 7, 8, 9, 999_999, 999_999);
+    #[test]
+    fn test_date_into_py_unchecked() {
+        Python::with_gil(|py| {
+            let date = Date::from_calendar_date(2012, Month::February, 29).unwrap();
+            let py_date = date_into_py_unchecked(py, date).unwrap();
+            assert!(py_date
+                .eq(new_py_datetime_ob(py, "date", (2012, 2, 29)))
+                .unwrap());
+
+            // The checked conversion reports out of range years itself
+            let date = Date::from_calendar_date(0, Month::January, 1).unwrap();
+            assert_eq!(
+                date.into_pyobject(py).unwrap_err().to_string(),
+                "ValueError: year 0 is out of range for Python's datetime: year must be positive, \
+                 as Python has no year 0 or years before the common era"
+            );
+        })
+    }
 
-    //         #[cfg(not(Py_GIL_DISABLED))]
-    //         assert_warnings!(
-    //             py,
-    //             check_utc("leap second", 2014, 5, 6, 7, 8, 59, 1_999_999, 999_999),
-    //             [(
-    //                 PyUserWarning,
-    //                 "ignored leap-second, `datetime` does not support leap-seconds"
-    //             )]
-    //         );
-    //     })
-    // }
+    #[cfg(feature = "large-dates")]
+    #[test]
+    fn test_date_into_pyobject_large_dates_out_of_range() {
+        Python::with_gil(|py| {
+            let bce = Date::from_calendar_date(-44_000, Month::March, 15).unwrap();
+            assert_eq!(
+                bce.into_pyobject(py).unwrap_err().to_string(),
+                "ValueError: year -44000 is out of range for Python's datetime: year must be \
+                 positive, as Python has no year 0 or years before the common era"
+            );
 
-    // #[test]
-    // fn test_pyo3_datetime_into_pyobject_fixed_offset() {
-    //     Python::with_gil(|py| {
-    //         let check_fixed_offset =
-    //             |name: &'static str, year, month, day, hour, minute, second, ms, py_ms| {
-    //                 let offset = FixedOffset::east_opt(3600).unwrap();
-    //                 let datetime = Date::from_ymd_opt(year, month, day)
-    //                     .unwrap()
-    //                     .and_hms_micro_opt(hour, minute, second, ms)
-    //                     .unwrap()
-    //                     .and_local_timezone(offset)
-    //                     .unwrap();
-    //                 let datetime = datetime.into_pyobject(py).unwrap();
-    //                 let py_tz = offset.into_pyobject(py).unwrap();
-    //                 let py_datetime = new_py_datetime_ob(
-    //                     py,
-    //                     "datetime",
-    //                     (year, month, day, hour, minute, second, py_ms, py_tz),
-    //                 );
-    //                 assert_eq!(
-    //                     datetime.compare(&py_datetime).unwrap(),
-    //                     Ordering::Equal,
-    //                     "{}: {} != {}",
-    //                     name,
-    //                     datetime,
-    //                     py_datetime
-    //                 );
-    //             };
-
-    //         check_fixed_offset("regular", 2014, 5, 6, 7, 8, 9, 999_999, 999_999);
+            let far_future = Date::from_calendar_date(10_000, Month::January, 1).unwrap();
+            assert_eq!(
+                far_future.into_pyobject(py).unwrap_err().to_string(),
+                "ValueError: year 10000 is out of range for Python's datetime: year must be at \
+                 most 9999 (MAXYEAR)"
+            );
+        })
+    }
 
-    //         #[cfg(not(Py_GIL_DISABLED))]
-    //         assert_warnings!(
-    //             py,
-    //             check_fixed_offset("leap second", 2014, 5, 6, 7, 8, 59, 1_999_999, 999_999),
-    //             [(
-    //                 PyUserWarning,
-    //                 "ignored leap-second, `datetime` does not support leap-seconds"
-    //             )]
-    //         );
-    //     })
-    // }
+    #[test]
+    fn test_epoch_seconds_from_int() {
+        Python::with_gil(|py| {
+            let check = |timestamp: i64| {
+                let EpochSeconds(dt) = timestamp.into_pyobject(py).unwrap().extract().unwrap();
+                assert_eq!(dt, OffsetDateTime::from_unix_timestamp(timestamp).unwrap());
+                assert_eq!(dt.offset(), UtcOffset::UTC);
+            };
+            check(1_700_000_000);
+            check(0);
+            check(-86_400);
+
+            // Aware datetimes still go through the regular conversion
+            let py_datetime =
+                new_py_datetime_ob(py, "datetime", (1970, 1, 2, 0, 0, 0, 0, python_utc(py)));
+            let EpochSeconds(dt) = py_datetime.extract().unwrap();
+            assert_eq!(dt.unix_timestamp(), 86_400);
+
+            assert!(i64::MAX
+                .into_pyobject(py)
+                .unwrap()
+                .extract::<EpochSeconds>()
+                .is_err());
+            assert!(py.None().into_bound(py).extract::<EpochSeconds>().is_err());
+            for b in [true, false] {
+                let err = PyBool::new(py, b).extract::<EpochSeconds>().unwrap_err();
+                assert!(err.is_instance_of::<PyTypeError>(py));
+            }
+        })
+    }
+
+    #[test]
+    fn test_pyo3_datetime_into_pyobject_utc() {
+        Python::with_gil(|py| {
+            let check_utc = |name: &'static str, year, month, day, hour, minute, second, nano| {
+                let month = Month::try_from(month).unwrap();
+                let datetime = Date::from_calendar_date(year, month, day)
+                    .unwrap()
+                    .with_hms_nano(hour, minute, second, nano)
+                    .unwrap()
+                    .assume_utc();
+                let py_datetime = datetime.into_pyobject(py).unwrap();
+                assert!(
+                    py_datetime.getattr("tzinfo").unwrap().is(&python_utc(py)),
+                    "{}",
+                    name
+                );
+                assert_datetime_eq_microsecond(&py_datetime, datetime);
+            };
+
+            check_utc("regular", 2014, 5, 6, 7, 8, 9, 999_999_000);
+            check_utc("sub-microsecond", 2014, 5, 6, 7, 8, 9, 1_999);
+            check_utc("last nanosecond", 2014, 5, 6, 7, 8, 9, 999_999_999);
+        })
+    }
+
+    #[test]
+    fn test_pyo3_datetime_into_pyobject_fixed_offset() {
+        Python::with_gil(|py| {
+            let check_fixed_offset = |year, month, day, hour, minute, second, nano| {
+                let offset = UtcOffset::from_hms(1, 0, 0).unwrap();
+                let datetime = Date::from_calendar_date(year, Month::try_from(month).unwrap(), day)
+                    .unwrap()
+                    .with_hms_nano(hour, minute, second, nano)
+                    .unwrap()
+                    .assume_offset(offset);
+                let py_datetime = datetime.into_pyobject(py).unwrap();
+                assert_datetime_eq_microsecond(&py_datetime, datetime);
+            };
+
+            check_fixed_offset(2014, 5, 6, 7, 8, 9, 999_999_000);
+            check_fixed_offset(2014, 5, 6, 7, 8, 9, 1_999);
+            check_fixed_offset(2014, 5, 6, 7, 8, 9, 999_999_999);
+        })
+    }
 
     // #[test]
     // fn test_pyo3_datetime_frompyobject_utc() {
@@ -1395,6 +4303,3925 @@ mod tests_time {
     //     })
     // }
 
+    #[test]
+    fn test_ctime_string() {
+        Python::with_gil(|py| {
+            let check = |date: Date, time: Time| {
+                let datetime = PrimitiveDateTime::new(date, time);
+                let py_datetime = datetime.into_pyobject(py).unwrap();
+                assert_eq!(
+                    primitive_datetime_to_ctime_string(py, datetime)
+                        .unwrap()
+                        .to_string(),
+                    py_datetime.call_method0("ctime").unwrap().to_string()
+                );
+                let py_date = date.into_pyobject(py).unwrap();
+                assert_eq!(
+                    date_to_ctime_string(py, date).unwrap().to_string(),
+                    py_date.call_method0("ctime").unwrap().to_string()
+                );
+            };
+            check(
+                Date::from_calendar_date(2021, Month::June, 9).unwrap(),
+                Time::from_hms(12, 34, 56).unwrap(),
+            );
+            check(
+                Date::from_calendar_date(1, Month::January, 1).unwrap(),
+                Time::MIDNIGHT,
+            );
+            check(
+                Date::from_calendar_date(9999, Month::December, 31).unwrap(),
+                Time::from_hms_nano(23, 59, 59, 999_999_999).unwrap(),
+            );
+            assert_eq!(
+                date_to_ctime_string(py, Date::from_calendar_date(2021, Month::June, 9).unwrap())
+                    .unwrap()
+                    .to_string(),
+                "Wed Jun  9 00:00:00 2021"
+            );
+        })
+    }
+
+    #[test]
+    fn test_duration_microseconds_i64() {
+        Python::with_gil(|py| {
+            let check = |duration: Duration, micros: i64| {
+                let py_int = duration_to_microseconds_i64(py, duration).unwrap();
+                assert_eq!(py_int.extract::<i64>().unwrap(), micros);
+                assert_eq!(
+                    duration_from_microseconds_i64(&py_int).unwrap(),
+                    Duration::microseconds(micros)
+                );
+            };
+            check(Duration::ZERO, 0);
+            check(
+                Duration::days(1) + Duration::nanoseconds(1999),
+                86_400_000_001,
+            );
+            check(Duration::nanoseconds(-1999), -1);
+            check(Duration::microseconds(i64::MAX), i64::MAX);
+            check(Duration::microseconds(i64::MIN), i64::MIN);
+
+            let too_long = Duration::microseconds(i64::MAX) + Duration::microseconds(1);
+            assert_eq!(
+                duration_to_microseconds_i64(py, too_long)
+                    .unwrap_err()
+                    .to_string(),
+                "OverflowError: duration does not fit in i64 microseconds"
+            );
+            let too_large = (i128::from(i64::MAX) + 1).into_pyobject(py).unwrap();
+            assert!(duration_from_microseconds_i64(&too_large).is_err());
+        })
+    }
+
+    #[test]
+    fn test_extract_primitive_datetime_fold_policy() {
+        Python::with_gil(|py| {
+            let py_datetime = new_py_datetime_ob(py, "datetime", (2021, 11, 7, 1, 30, 0, 0))
+                .call_method(
+                    "replace",
+                    (),
+                    Some(&[("fold", 1)].into_py_dict(py).unwrap()),
+                )
+                .unwrap();
+            let expected = PrimitiveDateTime::new(
+                Date::from_calendar_date(2021, Month::November, 7).unwrap(),
+                Time::from_hms(1, 30, 0).unwrap(),
+            );
+
+            assert_eq!(
+                py_datetime.extract::<PrimitiveDateTime>().unwrap(),
+                expected
+            );
+            assert_eq!(
+                extract_primitive_datetime(&py_datetime, FoldPolicy::Ignore).unwrap(),
+                expected
+            );
+            assert_eq!(
+                extract_primitive_datetime(&py_datetime, FoldPolicy::Error)
+                    .unwrap_err()
+                    .to_string(),
+                "ValueError: discarded fold=1 of a naive datetime"
+            );
+
+            #[cfg(not(Py_GIL_DISABLED))]
+            assert_warnings!(
+                py,
+                assert_eq!(
+                    extract_primitive_datetime(&py_datetime, FoldPolicy::Warn).unwrap(),
+                    expected
+                ),
+                [(PyUserWarning, "discarded fold=1 of a naive datetime")]
+            );
+
+            // Without a fold no policy has any effect
+            let py_datetime = new_py_datetime_ob(py, "datetime", (2021, 11, 7, 1, 30, 0, 0));
+            assert_eq!(
+                extract_primitive_datetime(&py_datetime, FoldPolicy::Error).unwrap(),
+                expected
+            );
+        })
+    }
+
+    #[test]
+    fn test_date_to_timetuple() {
+        Python::with_gil(|py| {
+            let check = |year, month, day| {
+                let date = Date::from_calendar_date(year, month, day).unwrap();
+                let timetuple = date_to_timetuple(py, date).unwrap();
+                let py_timetuple = date
+                    .into_pyobject(py)
+                    .unwrap()
+                    .call_method0("timetuple")
+                    .unwrap();
+                assert!(
+                    timetuple.eq(&py_timetuple).unwrap(),
+                    "{} != {}",
+                    timetuple,
+                    py_timetuple
+                );
+            };
+            check(2021, Month::June, 9);
+            check(2024, Month::December, 31);
+            check(2023, Month::December, 31);
+            check(1, Month::January, 1);
+            check(9999, Month::December, 31);
+        })
+    }
+
+    #[test]
+    fn test_tz_repr_policy() {
+        Python::with_gil(|py| {
+            assert_eq!(tz_repr_policy(), TzReprPolicy::FixedOffset);
+            let fixed_utc = UtcOffset::UTC.into_pyobject(py).unwrap();
+            assert!(fixed_utc.eq(python_utc(py)).unwrap());
+
+            set_tz_repr_policy(TzReprPolicy::PreferUtcSingleton);
+            let utc = UtcOffset::UTC.into_pyobject(py).unwrap();
+            assert!(utc.is(&python_utc(py)));
+            let dt = OffsetDateTime::UNIX_EPOCH.into_pyobject(py).unwrap();
+            assert!(dt.getattr("tzinfo").unwrap().is(&python_utc(py)));
+            let offset = UtcOffset::from_hms(1, 0, 0).unwrap();
+            let tz = offset.into_pyobject(py).unwrap();
+            assert_eq!(tz.extract::<UtcOffset>().unwrap(), offset);
+
+            assert_eq!(
+                set_tz_repr_policy(TzReprPolicy::FixedOffset),
+                TzReprPolicy::PreferUtcSingleton
+            );
+        })
+    }
+
+    #[test]
+    // Only Python>=3.9 has the zoneinfo package
+    // We skip the test on windows too since we'd need to install
+    // tzdata there to make this work.
+    #[cfg(all(Py_3_9, not(target_os = "windows")))]
+    fn test_tz_repr_policy_zoneinfo() {
+        Python::with_gil(|py| {
+            set_tz_repr_policy(TzReprPolicy::PreferZoneInfoKey);
+            let key = |offset: UtcOffset| {
+                let tz = offset.into_pyobject(py).unwrap();
+                tz.getattr("key")
+                    .ok()
+                    .map(|key| key.extract::<String>().unwrap())
+            };
+            assert_eq!(key(UtcOffset::UTC).as_deref(), Some("UTC"));
+            assert_eq!(
+                key(UtcOffset::from_hms(5, 0, 0).unwrap()).as_deref(),
+                Some("Etc/GMT-5")
+            );
+            assert_eq!(
+                key(UtcOffset::from_hms(-12, 0, 0).unwrap()).as_deref(),
+                Some("Etc/GMT+12")
+            );
+            assert_eq!(key(UtcOffset::from_hms(5, 30, 0).unwrap()), None);
+            assert_eq!(key(UtcOffset::from_hms(-13, 0, 0).unwrap()), None);
+
+            let dt = OffsetDateTime::UNIX_EPOCH
+                .to_offset(UtcOffset::from_hms(5, 0, 0).unwrap())
+                .into_pyobject(py)
+                .unwrap();
+            assert_eq!(
+                dt.extract::<OffsetDateTime>().unwrap(),
+                OffsetDateTime::UNIX_EPOCH
+            );
+            set_tz_repr_policy(TzReprPolicy::FixedOffset);
+        })
+    }
+
+    #[test]
+    fn test_utc_offset_frompyobject_named_timezone() {
+        Python::with_gil(|py| {
+            let td = new_py_datetime_ob(py, "timedelta", (0, 7200, 0));
+            let py_tz = new_py_datetime_ob(py, "timezone", (td, "CEST"));
+            assert_eq!(
+                py_tz
+                    .call_method1("tzname", (py.None(),))
+                    .unwrap()
+                    .to_string(),
+                "CEST"
+            );
+            let offset: UtcOffset = py_tz.extract().unwrap();
+            assert_eq!(offset, UtcOffset::from_hms(2, 0, 0).unwrap());
+
+            let py_datetime = new_py_datetime_ob(py, "datetime", (2024, 7, 1, 12, 0, 0, 0, py_tz));
+            let dt: OffsetDateTime = py_datetime.extract().unwrap();
+            assert_eq!(dt.offset(), UtcOffset::from_hms(2, 0, 0).unwrap());
+        })
+    }
+
+    #[test]
+    // Sub-interpreters can only be told apart from Python 3.9 on
+    #[cfg(all(
+        Py_3_9,
+        not(any(PyPy, GraalPy)),
+        not(all(windows, Py_LIMITED_API, not(Py_3_10)))
+    ))]
+    fn test_caches_reject_second_interpreter() {
+        Python::with_gil(|py| {
+            let offset = UtcOffset::from_hms(1, 0, 0).unwrap();
+            let date = Date::from_calendar_date(2021, Month::June, 9).unwrap();
+            let time = Time::from_hms(12, 34, 56).unwrap();
+            // Fill the caches in this interpreter
+            offset.into_pyobject(py).unwrap();
+            combine_into_py(py, date, time, None).unwrap();
+            py_datetime_resolution(py).unwrap();
+            let main_id = current_interpreter_id(py).unwrap();
+
+            let errors: Vec<bool> = unsafe {
+                let main = ffi::PyThreadState_Get();
+                let sub = ffi::Py_NewInterpreter();
+                assert!(!sub.is_null());
+                // `py` now refers to the sub-interpreter, which is current until it is ended.
+                // Nothing created in it may outlive it, so only the outcomes are kept.
+                assert_ne!(current_interpreter_id(py).unwrap(), main_id);
+                let is_import_error = |result: PyResult<Bound<'_, PyAny>>| {
+                    result.map_or_else(|err| err.is_instance_of::<PyImportError>(py), |_| false)
+                };
+                let errors = vec![
+                    is_import_error(offset.into_pyobject(py).map(BoundObject::into_any)),
+                    is_import_error(
+                        combine_into_py(py, date, time, None).map(BoundObject::into_any),
+                    ),
+                    is_import_error(py_datetime_resolution(py).map(BoundObject::into_any)),
+                ];
+                ffi::Py_EndInterpreter(sub);
+                ffi::PyThreadState_Swap(main);
+                errors
+            };
+            assert_eq!(errors, vec![true; 3]);
+
+            // The first interpreter can still use its caches
+            assert_eq!(current_interpreter_id(py).unwrap(), main_id);
+            assert_eq!(
+                offset
+                    .into_pyobject(py)
+                    .unwrap()
+                    .extract::<UtcOffset>()
+                    .unwrap(),
+                offset
+            );
+        })
+    }
+
+    #[test]
+    fn test_offset_datetime_to_isoformat() {
+        Python::with_gil(|py| {
+            let check = |datetime: OffsetDateTime| {
+                let py_datetime = datetime.into_pyobject(py).unwrap();
+                assert_eq!(
+                    offset_datetime_to_isoformat(py, &datetime, false)
+                        .unwrap()
+                        .to_string(),
+                    py_datetime.call_method0("isoformat").unwrap().to_string()
+                );
+                let kwargs = [("timespec", "microseconds")].into_py_dict(py).unwrap();
+                assert_eq!(
+                    offset_datetime_to_isoformat(py, &datetime, true)
+                        .unwrap()
+                        .to_string(),
+                    py_datetime
+                        .call_method("isoformat", (), Some(&kwargs))
+                        .unwrap()
+                        .to_string()
+                );
+            };
+            let date = Date::from_calendar_date(2021, Month::June, 9).unwrap();
+            check(date.with_hms(12, 34, 56).unwrap().assume_utc());
+            check(
+                date.with_hms_micro(12, 34, 56, 789_012)
+                    .unwrap()
+                    .assume_offset(UtcOffset::from_hms(5, 30, 0).unwrap()),
+            );
+            check(
+                date.with_hms_nano(0, 0, 0, 1_500)
+                    .unwrap()
+                    .assume_offset(UtcOffset::from_hms(-3, -25, -7).unwrap()),
+            );
+
+            let datetime = date.with_hms(12, 34, 56).unwrap().assume_utc();
+            assert_eq!(
+                offset_datetime_to_isoformat(py, &datetime, false)
+                    .unwrap()
+                    .to_string(),
+                "2021-06-09T12:34:56+00:00"
+            );
+            assert_eq!(
+                offset_datetime_to_isoformat(py, &datetime, true)
+                    .unwrap()
+                    .to_string(),
+                "2021-06-09T12:34:56.000000+00:00"
+            );
+        })
+    }
+
+    #[test]
+    fn test_time_to_isoformat() {
+        Python::with_gil(|py| {
+            let check = |time: Time| {
+                let py_time = time.into_pyobject(py).unwrap();
+                for (timespec, name) in [
+                    (TimeSpec::Auto, "auto"),
+                    (TimeSpec::Hours, "hours"),
+                    (TimeSpec::Minutes, "minutes"),
+                    (TimeSpec::Seconds, "seconds"),
+                    (TimeSpec::Milliseconds, "milliseconds"),
+                    (TimeSpec::Microseconds, "microseconds"),
+                ] {
+                    let kwargs = [("timespec", name)].into_py_dict(py).unwrap();
+                    assert_eq!(
+                        time_to_isoformat(py, time, timespec).unwrap().to_string(),
+                        py_time
+                            .call_method("isoformat", (), Some(&kwargs))
+                            .unwrap()
+                            .to_string(),
+                        "{} with timespec={}",
+                        time,
+                        name
+                    );
+                }
+            };
+            check(Time::MIDNIGHT);
+            check(Time::from_hms(1, 2, 3).unwrap());
+            check(Time::from_hms_micro(12, 34, 56, 789_012).unwrap());
+            check(Time::from_hms_micro(23, 59, 59, 999_999).unwrap());
+            check(Time::from_hms_micro(23, 59, 59, 999).unwrap());
+            check(Time::from_hms_nano(7, 8, 9, 1_999).unwrap());
+        })
+    }
+
+    #[test]
+    fn test_duration_zero() {
+        Python::with_gil(|py| {
+            let py_zero = new_py_datetime_ob(py, "timedelta", (0, 0, 0));
+            for zero in [
+                Duration::ZERO,
+                -Duration::ZERO,
+                Duration::seconds(-1) + Duration::seconds(1),
+                Duration::days(1) - Duration::seconds(86_400),
+                Duration::nanoseconds(-999),
+            ] {
+                let py_delta = zero.into_pyobject(py).unwrap();
+                assert!(py_delta.eq(&py_zero).unwrap());
+                for attr in ["days", "seconds", "microseconds"] {
+                    assert_eq!(py_delta.getattr(attr).unwrap().extract::<i64>().unwrap(), 0);
+                }
+            }
+            assert_eq!(py_zero.extract::<Duration>().unwrap(), Duration::ZERO);
+        })
+    }
+
+    #[test]
+    fn test_offset_datetime_extract_with_tzinfo() {
+        Python::with_gil(|py| {
+            let td = new_py_datetime_ob(py, "timedelta", (0, 3600, 0));
+            let py_tz = new_py_datetime_ob(py, "timezone", (td, "CET"));
+            let py_datetime = new_py_datetime_ob(py, "datetime", (2024, 1, 1, 12, 0, 0, 0, &py_tz));
+            let (datetime, tzinfo) = offset_datetime_extract_with_tzinfo(&py_datetime).unwrap();
+            assert_eq!(datetime, py_datetime.extract::<OffsetDateTime>().unwrap());
+            assert!(tzinfo.bind(py).is(&py_tz));
+
+            let naive = new_py_datetime_ob(py, "datetime", (2024, 1, 1, 12, 0, 0, 0));
+            assert!(offset_datetime_extract_with_tzinfo(&naive).is_err());
+        })
+    }
+
+    /// Asserts that `py_datetime` is equal to `datetime` at microsecond resolution.
+    ///
+    /// Python's `datetime` cannot hold sub-microsecond precision, so any nanoseconds beyond the
+    /// microsecond are expected, and intended, to be truncated by the conversion.
+    fn assert_datetime_eq_microsecond(py_datetime: &Bound<'_, PyAny>, datetime: OffsetDateTime) {
+        let py = py_datetime.py();
+        let truncated = datetime
+            .replace_nanosecond(datetime.microsecond() * 1000)
+            .unwrap();
+        let offset = new_py_datetime_ob(py, "timedelta", (0, datetime.offset().whole_seconds()));
+        let expected = new_py_datetime_ob(
+            py,
+            "datetime",
+            (
+                datetime.year(),
+                u8::from(datetime.month()),
+                datetime.day(),
+                datetime.hour(),
+                datetime.minute(),
+                datetime.second(),
+                datetime.microsecond(),
+                new_py_datetime_ob(py, "timezone", (offset,)),
+            ),
+        );
+        assert_eq!(
+            py_datetime.compare(&expected).unwrap(),
+            Ordering::Equal,
+            "{} != {}",
+            py_datetime,
+            datetime
+        );
+        assert_eq!(py_datetime.extract::<OffsetDateTime>().unwrap(), truncated);
+    }
+
+    #[test]
+    fn test_offset_datetime_into_py_named() {
+        Python::with_gil(|py| {
+            let datetime = Date::from_calendar_date(2024, Month::March, 1)
+                .unwrap()
+                .with_hms(9, 0, 0)
+                .unwrap()
+                .assume_offset(UtcOffset::from_hms(5, 30, 0).unwrap());
+            let py_datetime = offset_datetime_into_py_named(py, &datetime, "IST").unwrap();
+            assert_eq!(
+                py_datetime.call_method0("tzname").unwrap().to_string(),
+                "IST"
+            );
+            assert_eq!(py_datetime.extract::<OffsetDateTime>().unwrap(), datetime);
+
+            assert_eq!(
+                offset_datetime_into_py_named(py, &datetime, "")
+                    .unwrap_err()
+                    .to_string(),
+                "ValueError: tzname must not be empty"
+            );
+        })
+    }
+
+    #[test]
+    fn test_out_of_range_tzinfo_offset() {
+        Python::with_gil(|py| {
+            let locals = crate::types::PyDict::new(py);
+            py.run(
+                ffi::c_str!(
+                    r#"
+import datetime
+
+class Malicious(datetime.tzinfo):
+    def utcoffset(self, dt):
+        return datetime.timedelta(hours=30)
+
+tz = Malicious()
+dt = datetime.datetime(2024, 1, 1, tzinfo=tz)
+"#
+                ),
+                Some(&locals),
+                None,
+            )
+            .unwrap();
+
+            let tz = locals.get_item("tz").unwrap().unwrap();
+            assert_eq!(
+                tz.extract::<UtcOffset>().unwrap_err().to_string(),
+                "ValueError: offset must be strictly between -24 hours and 24 hours, \
+                 got datetime.timedelta(days=1, seconds=21600)"
+            );
+            // Python validates the offset itself when going through the datetime
+            let dt = locals.get_item("dt").unwrap().unwrap();
+            assert!(dt
+                .extract::<OffsetDateTime>()
+                .unwrap_err()
+                .is_instance_of::<PyValueError>(py));
+        })
+    }
+
+    #[test]
+    fn test_extract_offset_datetime_range() {
+        Python::with_gil(|py| {
+            let offset = UtcOffset::from_hms(2, 0, 0).unwrap();
+            let start = OffsetDateTime::UNIX_EPOCH.to_offset(offset);
+            let py_start = start.into_pyobject(py).unwrap();
+            // The stop may use a different timezone, only the instant matters
+            let py_stop = (OffsetDateTime::UNIX_EPOCH + Duration::hours(3))
+                .into_pyobject(py)
+                .unwrap();
+            let step = Duration::hours(1).into_pyobject(py).unwrap();
+            let range = extract_offset_datetime_range(&py_start, &py_stop, &step).unwrap();
+            assert_eq!(
+                range,
+                [
+                    start,
+                    start + Duration::hours(1),
+                    start + Duration::hours(2)
+                ]
+            );
+            assert!(range.iter().all(|dt| dt.offset() == offset));
+
+            // An empty range
+            let range = extract_offset_datetime_range(&py_stop, &py_start, &step).unwrap();
+            assert!(range.is_empty());
+
+            for step in [Duration::ZERO, Duration::hours(-1)] {
+                let step = step.into_pyobject(py).unwrap();
+                assert_eq!(
+                    extract_offset_datetime_range(&py_start, &py_stop, &step)
+                        .unwrap_err()
+                        .to_string(),
+                    "ValueError: step must be a positive timedelta"
+                );
+            }
+        })
+    }
+
+    #[test]
+    fn test_utc_offset_timezone_cache() {
+        Python::with_gil(|py| {
+            let offset = UtcOffset::from_hms(3, 0, 0).unwrap();
+            let tz = offset.into_pyobject(py).unwrap();
+            assert!(tz.is(&offset.into_pyobject(py).unwrap()));
+            let dt = OffsetDateTime::UNIX_EPOCH.to_offset(offset);
+            assert!(tz.is(&dt.into_pyobject(py).unwrap().getattr("tzinfo").unwrap()));
+
+            // Using more offsets than fit in the cache evicts the least recently used ones
+            let other_offsets = (1..=TIMEZONE_CACHE_SIZE as i8)
+                .map(|minutes| UtcOffset::from_hms(-1, -minutes, 0).unwrap());
+            for other in other_offsets {
+                other.into_pyobject(py).unwrap();
+            }
+            let new_tz = offset.into_pyobject(py).unwrap();
+            assert!(!tz.is(&new_tz));
+            assert!(tz.eq(&new_tz).unwrap());
+        })
+    }
+
+    #[test]
+    fn test_primitive_datetime_to_isoformat() {
+        Python::with_gil(|py| {
+            let check = |datetime: PrimitiveDateTime| {
+                let py_datetime = datetime.into_pyobject(py).unwrap();
+                for sep in ['T', ' '] {
+                    for (timespec, name) in [
+                        (TimeSpec::Auto, "auto"),
+                        (TimeSpec::Hours, "hours"),
+                        (TimeSpec::Minutes, "minutes"),
+                        (TimeSpec::Seconds, "seconds"),
+                        (TimeSpec::Milliseconds, "milliseconds"),
+                        (TimeSpec::Microseconds, "microseconds"),
+                    ] {
+                        let kwargs = [("timespec", name)].into_py_dict(py).unwrap();
+                        assert_eq!(
+                            primitive_datetime_to_isoformat(py, datetime, timespec, sep)
+                                .unwrap()
+                                .to_string(),
+                            py_datetime
+                                .call_method("isoformat", (sep,), Some(&kwargs))
+                                .unwrap()
+                                .to_string(),
+                            "{} with sep={:?} timespec={}",
+                            datetime,
+                            sep,
+                            name
+                        );
+                    }
+                }
+            };
+            let date = Date::from_calendar_date(2021, Month::June, 9).unwrap();
+            check(date.midnight());
+            check(date.with_hms_micro(12, 34, 56, 789_012).unwrap());
+            check(
+                Date::from_calendar_date(1, Month::January, 1)
+                    .unwrap()
+                    .with_hms_nano(23, 59, 59, 999_999_999)
+                    .unwrap(),
+            );
+        })
+    }
+
+    #[test]
+    fn test_extract_unpickled_datetime() {
+        Python::with_gil(|py| {
+            let pickle = py.import("pickle").unwrap();
+            let unpickle = |obj, protocol: u8| {
+                let data = pickle.call_method1("dumps", (obj, protocol)).unwrap();
+                pickle.call_method1("loads", (data,)).unwrap()
+            };
+            let date = Date::from_calendar_date(2021, Month::June, 9).unwrap();
+            let naive = date.with_hms_micro(12, 34, 56, 789_012).unwrap();
+            let aware = naive.assume_offset(UtcOffset::from_hms(-5, -30, 0).unwrap());
+
+            // Protocol 0 uses a different, text based representation of the datetime state
+            for protocol in [0, 2, 5] {
+                let py_naive = unpickle(naive.into_pyobject(py).unwrap().into_any(), protocol);
+                assert_eq!(py_naive.extract::<PrimitiveDateTime>().unwrap(), naive);
+                let py_aware = unpickle(aware.into_pyobject(py).unwrap().into_any(), protocol);
+                assert_eq!(py_aware.extract::<OffsetDateTime>().unwrap(), aware);
+                let py_date = unpickle(date.into_pyobject(py).unwrap().into_any(), protocol);
+                assert_eq!(py_date.extract::<Date>().unwrap(), date);
+            }
+        })
+    }
+
+    #[test]
+    fn test_duration_from_parts_overflow() {
+        // The extremes of Python's timedelta sum without overflowing
+        assert_eq!(
+            duration_from_parts(999_999_999, 86_399, 999_999).unwrap(),
+            Duration::seconds(86_399_999_999_999) + Duration::microseconds(999_999)
+        );
+        assert_eq!(
+            duration_from_parts(-999_999_999, 0, 0).unwrap(),
+            Duration::seconds(-86_399_999_913_600)
+        );
+
+        // Anything out of `Duration`'s range is an error rather than a panic
+        Python::with_gil(|py| {
+            for (days, seconds, microseconds) in [
+                (i64::MAX, 0, 0),
+                (i64::MAX / 86_400, i64::MAX, 0),
+                (i64::MIN / 86_400, i64::MIN, -1_000_000),
+            ] {
+                let err = duration_from_parts(days, seconds, microseconds).unwrap_err();
+                assert!(err.is_instance_of::<PyOverflowError>(py));
+            }
+        });
+    }
+
+    #[test]
+    fn test_utc_offset_non_hour_aligned_roundtrip() {
+        Python::with_gil(|py| {
+            for (hours, minutes, seconds) in [
+                (5, 30, 0),    // India
+                (5, 45, 0),    // Nepal
+                (12, 45, 0),   // Chatham Islands
+                (-3, -30, 0),  // Newfoundland
+                (-9, -30, 0),  // Marquesas Islands
+                (0, -45, -30), // sub-minute offsets are allowed by Python too
+            ] {
+                let offset = UtcOffset::from_hms(hours, minutes, seconds).unwrap();
+                let py_tz = offset.into_pyobject(py).unwrap();
+                let td = new_py_datetime_ob(py, "timedelta", (0, offset.whole_seconds(), 0));
+                assert!(py_tz.eq(new_py_datetime_ob(py, "timezone", (td,))).unwrap());
+                assert_eq!(py_tz.extract::<UtcOffset>().unwrap(), offset);
+            }
+        })
+    }
+
+    #[test]
+    fn test_time_frompyobject_str() {
+        Python::with_gil(|py| {
+            let check = |s: &str, expected: Time| {
+                let time: Time = PyString::new(py, s).extract().unwrap();
+                assert_eq!(time, expected, "{}", s);
+            };
+            check("13:45:30", Time::from_hms(13, 45, 30).unwrap());
+            check("13:45:30.5", Time::from_hms_milli(13, 45, 30, 500).unwrap());
+            check(
+                "13:45:30.500000",
+                Time::from_hms_micro(13, 45, 30, 500_000).unwrap(),
+            );
+            check("00:00:00.000001", Time::from_hms_micro(0, 0, 0, 1).unwrap());
+
+            for invalid in ["24:00:00", "13:60:00", "13:45", "13:45:30.", "noon"] {
+                let err = PyString::new(py, invalid).extract::<Time>().unwrap_err();
+                assert!(err.is_instance_of::<PyValueError>(py), "{}", invalid);
+            }
+
+            // Other types still fail with the usual error
+            assert_eq!(
+                1i32.into_pyobject(py)
+                    .unwrap()
+                    .extract::<Time>()
+                    .unwrap_err()
+                    .to_string(),
+                "TypeError: 'int' object cannot be converted to 'PyTime'"
+            );
+        })
+    }
+
+    #[test]
+    fn test_combine_into_py() {
+        Python::with_gil(|py| {
+            let date = Date::from_calendar_date(2021, Month::June, 9).unwrap();
+            let time = Time::from_hms_micro(12, 34, 56, 789_012).unwrap();
+            let py_combine = py
+                .import("datetime")
+                .unwrap()
+                .getattr("datetime")
+                .unwrap()
+                .getattr("combine")
+                .unwrap();
+            let py_date = date.into_pyobject(py).unwrap();
+            let py_time = time.into_pyobject(py).unwrap();
+
+            let naive = combine_into_py(py, date, time, None).unwrap();
+            assert!(naive
+                .eq(py_combine.call1((&py_date, &py_time)).unwrap())
+                .unwrap());
+            assert!(naive.getattr("tzinfo").unwrap().is_none());
+            assert_eq!(
+                naive.extract::<PrimitiveDateTime>().unwrap(),
+                PrimitiveDateTime::new(date, time)
+            );
+
+            let utc = python_utc(py);
+            #[cfg(not(Py_LIMITED_API))]
+            let utc = utc.downcast_into::<PyTzInfo>().unwrap();
+            let aware = combine_into_py(py, date, time, Some(&utc)).unwrap();
+            assert!(aware.getattr("tzinfo").unwrap().is(&utc));
+            assert_eq!(
+                aware.extract::<OffsetDateTime>().unwrap(),
+                PrimitiveDateTime::new(date, time).assume_utc()
+            );
+        })
+    }
+
+    #[test]
+    fn test_offset_datetime_utc_into_pyobject_is_utc_singleton() {
+        Python::with_gil(|py| {
+            let utc = python_utc(py);
+            let datetime = Date::from_calendar_date(2021, Month::June, 9)
+                .unwrap()
+                .with_hms(12, 34, 56)
+                .unwrap()
+                .assume_utc();
+            for policy in [TzReprPolicy::FixedOffset, TzReprPolicy::PreferUtcSingleton] {
+                let previous = set_tz_repr_policy(policy);
+                let py_datetime = datetime.into_pyobject(py).unwrap();
+                set_tz_repr_policy(previous);
+                assert!(
+                    py_datetime.getattr("tzinfo").unwrap().is(&utc),
+                    "{:?}",
+                    policy
+                );
+            }
+        })
+    }
+
+    #[test]
+    fn test_time_frompyobject_last_microsecond_is_not_leap_second() {
+        Python::with_gil(|py| {
+            let py_time = py
+                .import("datetime")
+                .unwrap()
+                .getattr("time")
+                .unwrap()
+                .call1((23, 59, 59, 999_999))
+                .unwrap();
+            let time: Time = py_time.extract().unwrap();
+            assert_eq!(time.second(), 59);
+            assert_eq!(time.nanosecond(), 999_999_000);
+            assert_eq!(time, Time::from_hms_micro(23, 59, 59, 999_999).unwrap());
+        })
+    }
+
+    #[test]
+    fn test_duration_into_pydelta_weeks() {
+        Python::with_gil(|py| {
+            for duration in [
+                Duration::ZERO,
+                Duration::weeks(1),
+                Duration::weeks(-3),
+                Duration::weeks(52 * 100),
+                Duration::days(8),
+                Duration::weeks(2) + Duration::microseconds(1),
+                Duration::weeks(-1) - Duration::seconds(1),
+            ] {
+                let weeks = duration_into_pydelta_weeks(py, duration).unwrap();
+                assert!(
+                    weeks.eq(duration.into_pyobject(py).unwrap()).unwrap(),
+                    "{}",
+                    duration
+                );
+                assert_eq!(weeks.extract::<Duration>().unwrap(), duration);
+            }
+        })
+    }
+
+    #[test]
+    fn test_offset_datetime_frompyobject_datetime_like() {
+        Python::with_gil(|py| {
+            let globals = [("datetime", py.import("datetime").unwrap())]
+                .into_py_dict(py)
+                .unwrap();
+            py.run(
+                ffi::c_str!(
+                    r#"
+class Wrapper:
+    def __init__(self, datetime):
+        self.datetime = datetime
+
+class DuckTyped:
+    year, month, day = 2021, 6, 9
+    hour, minute, second, microsecond = 12, 34, 56, 789012
+
+    def utcoffset(self):
+        return datetime.timedelta(hours=2)
+
+class Naive(DuckTyped):
+    def utcoffset(self):
+        return None
+
+class NaiveTz(datetime.tzinfo):
+    def utcoffset(self, dt):
+        return None
+
+naive_tz = NaiveTz()
+
+class Partial:
+    year, month, day = 2021, 6, 9
+
+tz = datetime.timezone(datetime.timedelta(hours=2))
+aware = datetime.datetime(2021, 6, 9, 12, 34, 56, 789012, tz)
+"#
+                ),
+                Some(&globals),
+                None,
+            )
+            .unwrap();
+            let eval = |code: &str| {
+                py.eval(&std::ffi::CString::new(code).unwrap(), Some(&globals), None)
+                    .unwrap()
+            };
+            let expected = Date::from_calendar_date(2021, Month::June, 9)
+                .unwrap()
+                .with_hms_micro(12, 34, 56, 789_012)
+                .unwrap()
+                .assume_offset(UtcOffset::from_hms(2, 0, 0).unwrap());
+
+            for code in ["Wrapper(aware)", "DuckTyped()"] {
+                assert_eq!(
+                    eval(code).extract::<OffsetDateTime>().unwrap(),
+                    expected,
+                    "{}",
+                    code
+                );
+            }
+
+            let naive = eval("Naive()");
+            let err = naive.extract::<OffsetDateTime>().unwrap_err();
+            assert!(err.is_instance_of::<PyTypeError>(py));
+            assert_eq!(
+                err.to_string(),
+                format!(
+                    "TypeError: {} returned None from utcoffset()",
+                    naive.repr().unwrap()
+                )
+            );
+
+            // A real datetime whose tzinfo returns None reports the tzinfo
+            let naive_tz = eval("naive_tz");
+            let err = eval("datetime.datetime(2021, 6, 9, tzinfo=naive_tz)")
+                .extract::<OffsetDateTime>()
+                .unwrap_err();
+            assert!(err.is_instance_of::<PyTypeError>(py));
+            assert_eq!(
+                err.to_string(),
+                format!(
+                    "TypeError: {} returned None from utcoffset()",
+                    naive_tz.repr().unwrap()
+                )
+            );
+
+            for (code, type_name) in [
+                ("Wrapper('2021-06-09')", "Wrapper"),
+                ("Partial()", "Partial"),
+            ] {
+                assert_eq!(
+                    eval(code)
+                        .extract::<OffsetDateTime>()
+                        .unwrap_err()
+                        .to_string(),
+                    format!(
+                        "TypeError: '{}' object cannot be converted to 'PyDateTime'",
+                        type_name
+                    )
+                );
+            }
+        })
+    }
+
+    #[test]
+    fn test_into_py_reporting() {
+        Python::with_gil(|py| {
+            for (time, truncated) in [
+                (Time::MIDNIGHT, false),
+                (Time::from_hms_micro(12, 34, 56, 789_012).unwrap(), false),
+                (Time::from_hms_nano(12, 34, 56, 789_012_345).unwrap(), true),
+                (Time::from_hms_nano(23, 59, 59, 999_999_999).unwrap(), true),
+            ] {
+                let (py_time, reported) = time_into_py_reporting(py, time).unwrap();
+                assert_eq!(reported, truncated, "{}", time);
+                assert!(py_time.eq(time.into_pyobject(py).unwrap()).unwrap());
+
+                let datetime = Date::from_calendar_date(2021, Month::June, 9)
+                    .unwrap()
+                    .with_time(time)
+                    .assume_offset(UtcOffset::from_hms(-3, 0, 0).unwrap());
+                let (py_datetime, reported) =
+                    offset_datetime_into_py_reporting(py, &datetime).unwrap();
+                assert_eq!(reported, truncated, "{}", datetime);
+                assert!(py_datetime.eq(datetime.into_pyobject(py).unwrap()).unwrap());
+            }
+        })
+    }
+
+    #[test]
+    fn test_primitive_datetime_from_aware_wall_clock() {
+        Python::with_gil(|py| {
+            let offset = Duration::minutes(5 * 60 + 30).into_pyobject(py).unwrap();
+            let tz = new_py_datetime_ob(py, "timezone", (offset,));
+            let py_datetime = new_py_datetime_ob(py, "datetime", (2021, 6, 9, 1, 2, 3, 4, tz));
+            assert_eq!(
+                primitive_datetime_from_aware_wall_clock(&py_datetime).unwrap(),
+                Date::from_calendar_date(2021, Month::June, 9)
+                    .unwrap()
+                    .with_hms_micro(1, 2, 3, 4)
+                    .unwrap()
+            );
+
+            let naive = new_py_datetime_ob(py, "datetime", (2021, 6, 9, 1, 2, 3, 4));
+            assert_eq!(
+                primitive_datetime_from_aware_wall_clock(&naive)
+                    .unwrap_err()
+                    .to_string(),
+                "TypeError: expected a datetime with non-None tzinfo"
+            );
+        })
+    }
+
+    #[test]
+    fn test_primitive_datetime_from_aware_utc() {
+        Python::with_gil(|py| {
+            let offset = Duration::minutes(5 * 60 + 30).into_pyobject(py).unwrap();
+            let tz = new_py_datetime_ob(py, "timezone", (offset,));
+            let py_datetime = new_py_datetime_ob(py, "datetime", (2021, 6, 9, 1, 2, 3, 4, &tz));
+            assert_eq!(
+                primitive_datetime_from_aware_utc(&py_datetime).unwrap(),
+                Date::from_calendar_date(2021, Month::June, 8)
+                    .unwrap()
+                    .with_hms_micro(19, 32, 3, 4)
+                    .unwrap()
+            );
+
+            let naive = new_py_datetime_ob(py, "datetime", (2021, 6, 9, 1, 2, 3, 4));
+            assert_eq!(
+                primitive_datetime_from_aware_utc(&naive)
+                    .unwrap_err()
+                    .to_string(),
+                "TypeError: expected a datetime with non-None tzinfo"
+            );
+        })
+    }
+
+    #[test]
+    // Only Python>=3.9 has the zoneinfo package
+    // We skip the test on windows too since we'd need to install
+    // tzdata there to make this work.
+    #[cfg(all(Py_3_9, not(target_os = "windows")))]
+    fn test_primitive_datetime_from_aware_utc_zoneinfo() {
+        Python::with_gil(|py| {
+            let zone = py
+                .import("zoneinfo")
+                .unwrap()
+                .getattr("ZoneInfo")
+                .unwrap()
+                .call1(("Europe/London",))
+                .unwrap();
+            let utc = |month, hour| {
+                let py_datetime =
+                    new_py_datetime_ob(py, "datetime", (2021, month, 9, hour, 0, 0, 0, &zone));
+                primitive_datetime_from_aware_utc(&py_datetime).unwrap()
+            };
+            let date = |month| Date::from_calendar_date(2021, month, 9).unwrap();
+            // GMT in winter, BST (+01:00) in summer
+            assert_eq!(utc(1, 12), date(Month::January).with_hms(12, 0, 0).unwrap());
+            assert_eq!(utc(7, 12), date(Month::July).with_hms(11, 0, 0).unwrap());
+        })
+    }
+
+    #[cfg(feature = "testing")]
+    #[test]
+    fn test_assert_roundtrip_stable() {
+        Python::with_gil(|py| {
+            let date = Date::from_calendar_date(2021, Month::June, 9).unwrap();
+            let time = Time::from_hms_nano(12, 34, 56, 789_012_345).unwrap();
+            let offset = UtcOffset::from_hms(-3, -30, 0).unwrap();
+            assert_roundtrip_stable(py, Duration::nanoseconds(-1_234_567_890));
+            assert_roundtrip_stable(py, Duration::days(-999_999_999));
+            assert_roundtrip_stable(py, date);
+            assert_roundtrip_stable(py, time);
+            assert_roundtrip_stable(py, PrimitiveDateTime::new(date, time));
+            assert_roundtrip_stable(py, PrimitiveDateTime::new(date, time).assume_offset(offset));
+        })
+    }
+
+    #[test]
+    fn test_offset_datetime_py_parts_dict() {
+        Python::with_gil(|py| {
+            let date = Date::from_calendar_date(2021, Month::June, 9).unwrap();
+            let time = Time::from_hms_micro(12, 34, 56, 789_012).unwrap();
+            for offset in [
+                UtcOffset::UTC,
+                UtcOffset::from_hms(5, 30, 0).unwrap(),
+                UtcOffset::from_hms(-9, -59, -59).unwrap(),
+                UtcOffset::from_hms(25, 59, 59).unwrap(),
+            ] {
+                let datetime = PrimitiveDateTime::new(date, time).assume_offset(offset);
+                let dict = offset_datetime_into_py_parts_dict(py, &datetime).unwrap();
+                assert!(dict
+                    .get_item("date")
+                    .unwrap()
+                    .unwrap()
+                    .eq(date.into_pyobject(py).unwrap())
+                    .unwrap());
+                assert!(dict
+                    .get_item("time")
+                    .unwrap()
+                    .unwrap()
+                    .eq(time.into_pyobject(py).unwrap())
+                    .unwrap());
+                assert_eq!(
+                    dict.get_item("offset")
+                        .unwrap()
+                        .unwrap()
+                        .extract::<i32>()
+                        .unwrap(),
+                    offset.whole_seconds()
+                );
+                let roundtripped = offset_datetime_from_py_parts_dict(&dict).unwrap();
+                assert_eq!(roundtripped, datetime);
+                assert_eq!(roundtripped.offset(), offset);
+            }
+
+            let dict = offset_datetime_into_py_parts_dict(
+                py,
+                &PrimitiveDateTime::new(date, time).assume_utc(),
+            )
+            .unwrap();
+            dict.set_item("offset", 26 * 3600).unwrap();
+            assert_eq!(
+                offset_datetime_from_py_parts_dict(&dict)
+                    .unwrap_err()
+                    .to_string(),
+                "ValueError: offset 93600 is out of range"
+            );
+            dict.del_item("time").unwrap();
+            let err = offset_datetime_from_py_parts_dict(&dict).unwrap_err();
+            assert!(err.is_instance_of::<PyKeyError>(py));
+            assert_eq!(err.to_string(), "KeyError: 'time'");
+        })
+    }
+
+    #[test]
+    fn test_timedelta_day_boundaries() {
+        Python::with_gil(|py| {
+            let timedelta = py.import("datetime").unwrap().getattr("timedelta").unwrap();
+            let days = |days: i64| {
+                let kwargs = [("days", days)].into_py_dict(py).unwrap();
+                timedelta.call((), Some(&kwargs))
+            };
+
+            // Exactly at Python's bounds extraction succeeds
+            for n in [-999_999_999, 999_999_999] {
+                let duration: Duration = days(n).unwrap().extract().unwrap();
+                assert_eq!(duration, Duration::days(n));
+            }
+
+            // One day beyond, Python itself raises a catchable OverflowError
+            for n in [-1_000_000_000, 1_000_000_000] {
+                let err = days(n).unwrap_err();
+                assert!(err.is_instance_of::<PyOverflowError>(py), "{}", err);
+
+                // ... and so does the conversion of the equivalent Duration, without panicking
+                let err = Duration::days(n).into_pyobject(py).unwrap_err();
+                assert!(err.is_instance_of::<PyOverflowError>(py), "{}", err);
+            }
+        })
+    }
+
+    #[test]
+    fn test_primitive_datetime_from_unix_float_utc() {
+        Python::with_gil(|py| {
+            let datetime = py.import("datetime").unwrap().getattr("datetime").unwrap();
+            let utc = python_utc(py);
+            for ts in [
+                0.0,
+                1.5,
+                -1.5,
+                1_623_242_096.789_012,
+                -86_400.000_001,
+                0.000_000_5,
+                0.000_001_5,
+                0.000_002_5,
+                -0.000_002_5,
+                253_402_300_799.5,
+                -62_135_596_800.0,
+            ] {
+                // The non-deprecated Python equivalent of utcfromtimestamp
+                let expected: PrimitiveDateTime = datetime
+                    .call_method1("fromtimestamp", (ts, &utc))
+                    .unwrap()
+                    .call_method(
+                        "replace",
+                        (),
+                        Some(&[("tzinfo", py.None())].into_py_dict(py).unwrap()),
+                    )
+                    .unwrap()
+                    .extract()
+                    .unwrap();
+                assert_eq!(
+                    primitive_datetime_from_unix_float_utc(py, ts).unwrap(),
+                    expected,
+                    "{}",
+                    ts
+                );
+            }
+
+            for ts in [
+                f64::NAN,
+                f64::INFINITY,
+                253_402_300_800.0,
+                -62_135_596_801.0,
+                1e300,
+            ] {
+                let err = primitive_datetime_from_unix_float_utc(py, ts).unwrap_err();
+                assert!(err.is_instance_of::<PyValueError>(py), "{}", ts);
+            }
+        })
+    }
+
+    #[test]
+    fn test_date_from_pandas_timestamp() {
+        Python::with_gil(|py| {
+            let globals = [("datetime", py.import("datetime").unwrap())]
+                .into_py_dict(py)
+                .unwrap();
+            // Stand-ins for pandas.Timestamp, to avoid depending on pandas
+            py.run(
+                ffi::c_str!(
+                    r#"
+class Timestamp(datetime.datetime):
+    pass
+
+Timestamp.__module__ = "pandas._libs.tslibs.timestamps"
+
+class Timestamp2:
+    year, month, day = 2021, 6, 9
+
+Timestamp2.__name__ = "Timestamp"
+Timestamp2.__module__ = "pandas"
+
+class NotPandas:
+    year, month, day = 2021, 6, 9
+"#
+                ),
+                Some(&globals),
+                None,
+            )
+            .unwrap();
+            let eval = |code: &str| {
+                py.eval(&std::ffi::CString::new(code).unwrap(), Some(&globals), None)
+                    .unwrap()
+            };
+            let expected = Date::from_calendar_date(2021, Month::June, 9).unwrap();
+
+            for code in [
+                "Timestamp(2021, 6, 9, 12, 34, 56)",
+                "Timestamp2()",
+                "datetime.date(2021, 6, 9)",
+            ] {
+                assert_eq!(
+                    date_from_pandas_timestamp(&eval(code)).unwrap(),
+                    expected,
+                    "{}",
+                    code
+                );
+            }
+
+            assert_eq!(
+                date_from_pandas_timestamp(&eval("NotPandas()"))
+                    .unwrap_err()
+                    .to_string(),
+                "TypeError: 'NotPandas' object cannot be converted to 'PyDate'"
+            );
+        })
+    }
+
+    #[test]
+    fn test_time_into_pyobject_day_boundary() {
+        Python::with_gil(|py| {
+            let check = |time: Time, args: (u8, u8, u8, u32)| {
+                let py_time = time.into_pyobject(py).unwrap();
+                assert!(
+                    py_time.eq(new_py_datetime_ob(py, "time", args)).unwrap(),
+                    "{} != {}",
+                    time,
+                    py_time
+                );
+            };
+
+            // Arithmetic wrapping around midnight gives 00:00, as Python has no 24:00
+            let wrapped = Time::from_hms(23, 59, 59).unwrap() + Duration::seconds(1);
+            assert_eq!(wrapped, Time::MIDNIGHT);
+            check(wrapped, (0, 0, 0, 0));
+            check(Time::MIDNIGHT, (0, 0, 0, 0));
+
+            // One nanosecond before midnight is truncated to the last microsecond of the day
+            let last = Time::MIDNIGHT - Duration::nanoseconds(1);
+            assert_eq!(last, Time::from_hms_nano(23, 59, 59, 999_999_999).unwrap());
+            check(last, (23, 59, 59, 999_999));
+            check(
+                Time::from_hms_nano(23, 59, 59, 999_999_000).unwrap(),
+                (23, 59, 59, 999_999),
+            );
+            check(
+                Time::from_hms_nano(23, 59, 59, 999_998_999).unwrap(),
+                (23, 59, 59, 999_998),
+            );
+        })
+    }
+
+    #[test]
+    // Only Python>=3.9 has the zoneinfo package
+    // We skip the test on windows too since we'd need to install
+    // tzdata there to make this work.
+    #[cfg(all(Py_3_9, not(target_os = "windows")))]
+    fn test_zoneinfo_from_key() {
+        Python::with_gil(|py| {
+            let zone = zoneinfo_from_key(py, "Europe/London").unwrap();
+            assert_eq!(zone.getattr("key").unwrap().to_string(), "Europe/London");
+            // ZoneInfo caches its instances, and so do we get the same one
+            assert!(zone.is(&zoneinfo_from_key(py, "Europe/London").unwrap()));
+
+            let err = zoneinfo_from_key(py, "Bogus/Zone").unwrap_err();
+            assert!(err.is_instance_of::<PyValueError>(py));
+            assert_eq!(
+                err.to_string(),
+                "ValueError: unknown time zone \"Bogus/Zone\""
+            );
+            let cause = err.cause(py).unwrap();
+            assert_eq!(
+                cause
+                    .value(py)
+                    .get_type()
+                    .getattr("__name__")
+                    .unwrap()
+                    .to_string(),
+                "ZoneInfoNotFoundError"
+            );
+        })
+    }
+
+    #[test]
+    // Only Python>=3.9 has the zoneinfo package
+    // We skip the test on windows too since we'd need to install
+    // tzdata there to make this work.
+    #[cfg(all(Py_3_9, not(target_os = "windows")))]
+    fn test_offset_datetime_into_zoneinfo_with_fold() {
+        Python::with_gil(|py| {
+            // London falls back from 02:00 BST to 01:00 GMT on 2021-10-31 at 01:00 UTC, so
+            // 01:30 local time occurs twice
+            let date = Date::from_calendar_date(2021, Month::October, 31).unwrap();
+            for (utc_hour, utc_minute, fold) in [(0, 30, 0), (1, 30, 1), (0, 59, 0), (1, 0, 1)] {
+                let datetime = date.with_hms(utc_hour, utc_minute, 0).unwrap().assume_utc();
+                let py_datetime =
+                    offset_datetime_into_zoneinfo_with_fold(py, &datetime, "Europe/London")
+                        .unwrap();
+                assert_eq!(
+                    py_datetime
+                        .getattr("fold")
+                        .unwrap()
+                        .extract::<u8>()
+                        .unwrap(),
+                    fold,
+                    "{}",
+                    datetime
+                );
+                assert_eq!(
+                    py_datetime
+                        .getattr("hour")
+                        .unwrap()
+                        .extract::<u8>()
+                        .unwrap(),
+                    1
+                );
+                assert_eq!(
+                    py_datetime
+                        .getattr("tzinfo")
+                        .unwrap()
+                        .getattr("key")
+                        .unwrap()
+                        .to_string(),
+                    "Europe/London"
+                );
+                // Python converts it back to the same instant
+                let roundtripped: OffsetDateTime = py_datetime.extract().unwrap();
+                assert_eq!(roundtripped, datetime);
+                let utc: OffsetDateTime = py_datetime
+                    .call_method1("astimezone", (python_utc(py),))
+                    .unwrap()
+                    .extract()
+                    .unwrap();
+                assert_eq!(utc, datetime);
+            }
+
+            assert!(offset_datetime_into_zoneinfo_with_fold(
+                py,
+                &date.midnight().assume_utc(),
+                "Bogus/Zone"
+            )
+            .is_err());
+        })
+    }
+
+    #[test]
+    fn test_utc_offset_frompyobject_zero_offsets() {
+        Python::with_gil(|py| {
+            let zero = Duration::ZERO.into_pyobject(py).unwrap();
+            for tz in [
+                python_utc(py),
+                new_py_datetime_ob(py, "timezone", (&zero,)),
+                new_py_datetime_ob(py, "timezone", (&zero, "Z")),
+                new_py_datetime_ob(py, "timezone", (&zero, "UTC")),
+            ] {
+                let offset: UtcOffset = tz.extract().unwrap();
+                assert_eq!(offset, UtcOffset::UTC, "{}", tz);
+            }
+        })
+    }
+
+    #[test]
+    fn test_negative_duration_into_pyobject_equals_python_negation() {
+        Python::with_gil(|py| {
+            let timedelta = py.import("datetime").unwrap().getattr("timedelta").unwrap();
+            let check = |duration: Duration, kwargs: &[(&str, i64)]| {
+                let kwargs = kwargs.into_py_dict(py).unwrap();
+                let expected = timedelta.call((), Some(&kwargs)).unwrap().neg().unwrap();
+                let py_duration = (-duration).into_pyobject(py).unwrap();
+                assert!(
+                    py_duration.eq(&expected).unwrap(),
+                    "{} != {}",
+                    py_duration,
+                    expected
+                );
+                // Also equal component by component, i.e. normalized the same way
+                for attr in ["days", "seconds", "microseconds"] {
+                    assert!(py_duration
+                        .getattr(attr)
+                        .unwrap()
+                        .eq(expected.getattr(attr).unwrap())
+                        .unwrap());
+                }
+            };
+
+            // -timedelta(hours=1) == timedelta(days=-1, seconds=82800)
+            check(Duration::hours(1), &[("hours", 1)]);
+            check(Duration::microseconds(1), &[("microseconds", 1)]);
+            check(Duration::days(1), &[("days", 1)]);
+            check(
+                Duration::days(1) + Duration::seconds(1),
+                &[("days", 1), ("seconds", 1)],
+            );
+            check(
+                Duration::days(3) + Duration::microseconds(500_001),
+                &[("days", 3), ("microseconds", 500_001)],
+            );
+            check(Duration::weeks(2), &[("weeks", 2)]);
+            check(Duration::days(999_999_999), &[("days", 999_999_999)]);
+        })
+    }
+
+    #[test]
+    fn test_py_datetime_resolution() {
+        Python::with_gil(|py| {
+            let resolution = py_datetime_resolution(py).unwrap();
+            assert_eq!(
+                resolution.extract::<Duration>().unwrap(),
+                Duration::microseconds(1)
+            );
+            assert!(resolution
+                .eq(new_py_datetime_ob(py, "timedelta", (0, 0, 1)))
+                .unwrap());
+            assert!(resolution.is(&py_datetime_resolution(py).unwrap()));
+        })
+    }
+
+    #[test]
+    fn test_optional_utc_offset_into_pyobject() {
+        Python::with_gil(|py| {
+            let offset = UtcOffset::from_hms(5, 30, 0).unwrap();
+
+            let py_offset = Some(offset).into_pyobject(py).unwrap();
+            assert_eq!(py_offset.extract::<UtcOffset>().unwrap(), offset);
+            assert!(None::<UtcOffset>.into_pyobject(py).unwrap().is_none());
+
+            let date = Date::from_calendar_date(2021, Month::June, 9).unwrap();
+            let time = Time::from_hms(12, 34, 56).unwrap();
+            let new_datetime = |offset: Option<UtcOffset>| {
+                let tzinfo = tzinfo_from_optional_offset(py, offset).unwrap();
+                #[cfg(not(Py_LIMITED_API))]
+                let datetime =
+                    PyDateTime::new(py, 2021, 6, 9, 12, 34, 56, 0, tzinfo.as_ref()).unwrap();
+                #[cfg(Py_LIMITED_API)]
+                let datetime =
+                    new_py_datetime_ob(py, "datetime", (2021, 6, 9, 12, 34, 56, 0, tzinfo));
+                datetime
+            };
+
+            let aware = new_datetime(Some(offset));
+            assert_eq!(
+                aware.extract::<OffsetDateTime>().unwrap(),
+                PrimitiveDateTime::new(date, time).assume_offset(offset)
+            );
+            let naive = new_datetime(None);
+            assert_eq!(
+                naive.extract::<PrimitiveDateTime>().unwrap(),
+                PrimitiveDateTime::new(date, time)
+            );
+        })
+    }
+
+    #[test]
+    fn test_truncate_nanos_to_micros() {
+        assert_eq!(truncate_nanos_to_micros(0), (0, false));
+        assert_eq!(truncate_nanos_to_micros(999), (0, true));
+        assert_eq!(truncate_nanos_to_micros(1_000), (1, false));
+        assert_eq!(truncate_nanos_to_micros(1_999), (1, true));
+        assert_eq!(truncate_nanos_to_micros(-1), (0, true));
+        assert_eq!(truncate_nanos_to_micros(-1_500), (-1, true));
+        assert_eq!(truncate_nanos_to_micros(-2_000), (-2, false));
+        assert_eq!(truncate_nanos_to_micros(999_999_999), (999_999, true));
+        assert_eq!(
+            truncate_nanos_to_micros(Duration::MAX.whole_nanoseconds()),
+            (Duration::MAX.whole_microseconds(), true)
+        );
+        assert_eq!(
+            truncate_nanos_to_micros(Duration::MIN.whole_nanoseconds()),
+            (Duration::MIN.whole_microseconds(), true)
+        );
+    }
+
+    #[test]
+    // Only Python>=3.9 has the zoneinfo package
+    // We skip the test on windows too since we'd need to install
+    // tzdata there to make this work.
+    #[cfg(all(Py_3_9, not(target_os = "windows")))]
+    fn test_offset_datetime_frompyobject_historical_sub_minute_offset() {
+        Python::with_gil(|py| {
+            let amsterdam = py
+                .import("zoneinfo")
+                .unwrap()
+                .getattr("ZoneInfo")
+                .unwrap()
+                .call1(("Europe/Amsterdam",))
+                .unwrap();
+            // Amsterdam Mean Time was +00:19:32, and +01:19:32 in summer
+            for (month, hours) in [(1, 0), (7, 1)] {
+                let py_datetime =
+                    new_py_datetime_ob(py, "datetime", (1930, month, 1, 12, 0, 0, 0, &amsterdam));
+                let datetime: OffsetDateTime = py_datetime.extract().unwrap();
+                assert_eq!(
+                    datetime.offset(),
+                    UtcOffset::from_hms(hours, 19, 32).unwrap()
+                );
+            }
+        })
+    }
+
+    #[test]
+    fn test_offset_datetime_frompyobject_fractional_second_offset() {
+        Python::with_gil(|py| {
+            let offset = Duration::seconds(37) + Duration::microseconds(1);
+            let tz = new_py_datetime_ob(py, "timezone", (offset.into_pyobject(py).unwrap(),));
+            let py_datetime = new_py_datetime_ob(py, "datetime", (2021, 6, 9, 12, 0, 0, 0, &tz));
+            assert_eq!(
+                py_datetime
+                    .extract::<OffsetDateTime>()
+                    .unwrap_err()
+                    .to_string(),
+                "ValueError: offset datetime.timedelta(seconds=37, microseconds=1) has a \
+                 fractional second, which UtcOffset cannot represent"
+            );
+        })
+    }
+
+    #[test]
+    fn test_date_proleptic_gregorian() {
+        // Both Python and `time` use the proleptic Gregorian calendar and never switch to the
+        // Julian calendar, so dates skipped by the 1582 calendar reform exist in both.
+        Python::with_gil(|py| {
+            // Historically, Julian 1582-10-04 was a Thursday followed by Gregorian 1582-10-15,
+            // but proleptically it is a Monday
+            for (year, month, day, weekday) in [
+                (1582, 10, 4, 0),
+                (1582, 10, 5, 1),
+                (1582, 10, 14, 3),
+                (1582, 10, 15, 4),
+                (1, 1, 1, 0),
+            ] {
+                let date =
+                    Date::from_calendar_date(year, Month::try_from(month).unwrap(), day).unwrap();
+                let py_date = date.into_pyobject(py).unwrap();
+                assert!(py_date
+                    .eq(new_py_datetime_ob(py, "date", (year, month, day)))
+                    .unwrap());
+                // Python's `weekday()` counts from Monday = 0
+                assert_eq!(
+                    py_date
+                        .call_method0("weekday")
+                        .unwrap()
+                        .extract::<u8>()
+                        .unwrap(),
+                    weekday
+                );
+                assert_eq!(date.weekday().number_days_from_monday(), weekday);
+                assert_eq!(
+                    py_date
+                        .call_method0("toordinal")
+                        .unwrap()
+                        .extract::<i32>()
+                        .unwrap(),
+                    date.to_julian_day() - 1_721_425
+                );
+            }
+        })
+    }
+
+    #[test]
+    fn test_utc_offset_odd_second_offsets() {
+        Python::with_gil(|py| {
+            for seconds in [37, -37, 86_399, -86_399, 3_600 + 59] {
+                let offset = UtcOffset::from_whole_seconds(seconds).unwrap();
+                let td = Duration::seconds(seconds.into()).into_pyobject(py).unwrap();
+                let tz = new_py_datetime_ob(py, "timezone", (td,));
+                assert_eq!(tz.extract::<UtcOffset>().unwrap(), offset, "{}", seconds);
+
+                let py_offset = offset.into_pyobject(py).unwrap();
+                assert!(py_offset.eq(&tz).unwrap(), "{}", seconds);
+
+                let datetime = Date::from_calendar_date(2021, Month::June, 9)
+                    .unwrap()
+                    .midnight()
+                    .assume_offset(offset);
+                let roundtripped: OffsetDateTime =
+                    datetime.into_pyobject(py).unwrap().extract().unwrap();
+                assert_eq!(roundtripped.offset(), offset, "{}", seconds);
+            }
+        })
+    }
+
+    #[test]
+    fn test_duration_divmod_into_py() {
+        Python::with_gil(|py| {
+            let divmod = py.import("builtins").unwrap().getattr("divmod").unwrap();
+            let values = [
+                Duration::ZERO,
+                Duration::hours(25),
+                -Duration::hours(25),
+                Duration::microseconds(7),
+                -Duration::microseconds(7),
+                Duration::days(400) + Duration::microseconds(123_456),
+                Duration::nanoseconds(-1_500),
+                Duration::days(999_999_999),
+            ];
+            let divisors = [
+                Duration::hours(1),
+                -Duration::hours(1),
+                Duration::microseconds(3),
+                -Duration::microseconds(3),
+                Duration::days(7),
+                -Duration::days(999_999_999),
+            ];
+            for duration in values {
+                for divisor in divisors {
+                    let result = duration_divmod_into_py(py, duration, divisor).unwrap();
+                    let expected = divmod
+                        .call1((
+                            duration.into_pyobject(py).unwrap(),
+                            divisor.into_pyobject(py).unwrap(),
+                        ))
+                        .unwrap();
+                    assert!(
+                        result.eq(&expected).unwrap(),
+                        "divmod({}, {}): {} != {}",
+                        duration,
+                        divisor,
+                        result,
+                        expected
+                    );
+                }
+            }
+
+            let err = duration_divmod_into_py(py, Duration::hours(1), Duration::nanoseconds(999))
+                .unwrap_err();
+            assert!(err.is_instance_of::<PyZeroDivisionError>(py));
+        })
+    }
+
+    #[test]
+    fn test_primitive_datetime_microsecond_roundtrip() {
+        Python::with_gil(|py| {
+            let date = Date::from_calendar_date(2021, Month::June, 9).unwrap();
+            for micro in [0, 1, 500_000, 999_999] {
+                let datetime = date.with_hms_micro(23, 59, 59, micro).unwrap();
+                let py_datetime = datetime.into_pyobject(py).unwrap();
+                assert_eq!(
+                    py_datetime
+                        .getattr("microsecond")
+                        .unwrap()
+                        .extract::<u32>()
+                        .unwrap(),
+                    micro
+                );
+                let roundtripped: PrimitiveDateTime = py_datetime.extract().unwrap();
+                assert_eq!(roundtripped, datetime);
+            }
+
+            // Sub-microsecond precision is truncated
+            let datetime = date.with_hms_nano(23, 59, 59, 999_999_999).unwrap();
+            let roundtripped: PrimitiveDateTime =
+                datetime.into_pyobject(py).unwrap().extract().unwrap();
+            assert_eq!(
+                roundtripped,
+                date.with_hms_micro(23, 59, 59, 999_999).unwrap()
+            );
+        })
+    }
+
+    #[test]
+    fn test_offset_datetime_frompyobject_utc_day_boundary() {
+        Python::with_gil(|py| {
+            let td = Duration::hours(5).into_pyobject(py).unwrap();
+            let tz = new_py_datetime_ob(py, "timezone", (td,));
+            let py_datetime = new_py_datetime_ob(py, "datetime", (2021, 1, 1, 1, 0, 0, 0, tz));
+            let datetime: OffsetDateTime = py_datetime.extract().unwrap();
+            assert_eq!(
+                datetime.date(),
+                Date::from_calendar_date(2021, Month::January, 1).unwrap()
+            );
+            assert_eq!(
+                datetime.to_offset(UtcOffset::UTC),
+                Date::from_calendar_date(2020, Month::December, 31)
+                    .unwrap()
+                    .with_hms(20, 0, 0)
+                    .unwrap()
+                    .assume_utc()
+            );
+        })
+    }
+
+    #[test]
+    fn test_offset_datetime_extract_or_default() {
+        Python::with_gil(|py| {
+            let default = OffsetDateTime::UNIX_EPOCH;
+            assert_eq!(
+                offset_datetime_extract_or_default(&py.None().into_bound(py), default).unwrap(),
+                default
+            );
+
+            let datetime = Date::from_calendar_date(2021, Month::June, 9)
+                .unwrap()
+                .with_hms(12, 34, 56)
+                .unwrap()
+                .assume_offset(UtcOffset::from_hms(2, 0, 0).unwrap());
+            let py_datetime = datetime.into_pyobject(py).unwrap();
+            assert_eq!(
+                offset_datetime_extract_or_default(&py_datetime, default).unwrap(),
+                datetime
+            );
+
+            // Only None is replaced, other invalid values are still errors
+            let naive = new_py_datetime_ob(py, "datetime", (2021, 6, 9, 12, 34, 56, 0));
+            assert!(offset_datetime_extract_or_default(&naive, default).is_err());
+            let int = 1i32.into_pyobject(py).unwrap();
+            assert!(offset_datetime_extract_or_default(&int, default).is_err());
+        })
+    }
+
+    #[test]
+    fn test_first_weekday_of_month_into_py() {
+        Python::with_gil(|py| {
+            for (year, month) in [(2021, 6), (2024, 2), (2024, 3), (1, 1), (9999, 12)] {
+                let weekday =
+                    first_weekday_of_month_into_py(py, year, Month::try_from(month).unwrap())
+                        .unwrap();
+                let expected = new_py_datetime_ob(py, "date", (year, month, 1))
+                    .call_method0("weekday")
+                    .unwrap();
+                assert!(weekday.as_any().eq(expected).unwrap(), "{}-{}", year, month);
+            }
+
+            assert!(first_weekday_of_month_into_py(py, 0, Month::January).is_err());
+        })
+    }
+
+    #[test]
+    fn test_offset_datetime_frompyobject_duck_typed_invalid_microsecond() {
+        Python::with_gil(|py| {
+            let globals = [("datetime", py.import("datetime").unwrap())]
+                .into_py_dict(py)
+                .unwrap();
+            py.run(
+                ffi::c_str!(
+                    r#"
+class Buggy:
+    year, month, day = 2021, 6, 9
+    hour, minute, second = 12, 34, 56
+
+    def __init__(self, microsecond):
+        self.microsecond = microsecond
+
+    def utcoffset(self):
+        return datetime.timedelta(0)
+"#
+                ),
+                Some(&globals),
+                None,
+            )
+            .unwrap();
+            let buggy = globals.get_item("Buggy").unwrap().unwrap();
+            for microsecond in [1_000_000, 2_000_000, u32::MAX] {
+                let err = buggy
+                    .call1((microsecond,))
+                    .unwrap()
+                    .extract::<OffsetDateTime>()
+                    .unwrap_err();
+                assert_eq!(
+                    err.to_string(),
+                    format!(
+                        "ValueError: microsecond must be in 0..=999999, got {}",
+                        microsecond
+                    )
+                );
+            }
+            let datetime = buggy
+                .call1((999_999,))
+                .unwrap()
+                .extract::<OffsetDateTime>()
+                .unwrap();
+            assert_eq!(datetime.microsecond(), 999_999);
+        })
+    }
+
+    #[test]
+    fn test_offset_datetime_parquet_timestamps() {
+        Python::with_gil(|py| {
+            let datetime = Date::from_calendar_date(2021, Month::June, 9)
+                .unwrap()
+                .with_hms_nano(12, 34, 56, 789_012_345)
+                .unwrap()
+                .assume_offset(UtcOffset::from_hms(2, 0, 0).unwrap());
+            let micros = offset_datetime_to_parquet_micros(py, &datetime).unwrap();
+            assert_eq!(micros.extract::<i64>().unwrap(), 1_623_234_896_789_012);
+            assert_eq!(
+                offset_datetime_from_parquet_micros(&micros).unwrap(),
+                datetime.to_offset(UtcOffset::UTC) - Duration::nanoseconds(345)
+            );
+            let nanos = offset_datetime_to_parquet_nanos(py, &datetime).unwrap();
+            assert_eq!(nanos.extract::<i64>().unwrap(), 1_623_234_896_789_012_345);
+            let roundtripped = offset_datetime_from_parquet_nanos(&nanos).unwrap();
+            assert_eq!(roundtripped, datetime);
+            assert_eq!(roundtripped.offset(), UtcOffset::UTC);
+
+            // Sub-microsecond precision before the epoch goes to the earlier microsecond
+            let before_epoch = OffsetDateTime::UNIX_EPOCH - Duration::nanoseconds(1);
+            assert_eq!(
+                offset_datetime_to_parquet_micros(py, &before_epoch)
+                    .unwrap()
+                    .extract::<i64>()
+                    .unwrap(),
+                -1
+            );
+
+            // The full range of `time` fits in microseconds, unless it is extended to ±999,999 years
+            for datetime in [
+                PrimitiveDateTime::MIN.assume_utc(),
+                PrimitiveDateTime::MAX.assume_utc(),
+            ] {
+                let micros = offset_datetime_to_parquet_micros(py, &datetime);
+                if cfg!(feature = "large-dates") {
+                    assert!(micros.unwrap_err().is_instance_of::<PyOverflowError>(py));
+                    continue;
+                }
+                assert_eq!(
+                    offset_datetime_from_parquet_micros(&micros.unwrap()).unwrap(),
+                    datetime - Duration::nanoseconds(datetime.nanosecond() as i64 % 1000)
+                );
+            }
+
+            // The i64 nanosecond boundaries
+            for nanos in [i64::MIN, i64::MAX] {
+                let py_nanos = nanos.into_pyobject(py).unwrap();
+                let datetime = offset_datetime_from_parquet_nanos(&py_nanos).unwrap();
+                assert_eq!(
+                    offset_datetime_to_parquet_nanos(py, &datetime)
+                        .unwrap()
+                        .extract::<i64>()
+                        .unwrap(),
+                    nanos
+                );
+                let beyond = datetime
+                    + if nanos > 0 {
+                        Duration::nanoseconds(1)
+                    } else {
+                        Duration::nanoseconds(-1)
+                    };
+                let err = offset_datetime_to_parquet_nanos(py, &beyond).unwrap_err();
+                assert!(err.is_instance_of::<PyOverflowError>(py));
+            }
+            assert_eq!(
+                offset_datetime_from_parquet_nanos(&i64::MAX.into_pyobject(py).unwrap()).unwrap(),
+                Date::from_calendar_date(2262, Month::April, 11)
+                    .unwrap()
+                    .with_hms_nano(23, 47, 16, 854_775_807)
+                    .unwrap()
+                    .assume_utc()
+            );
+
+            // Values which don't fit in an i64 are rejected
+            let too_large = (i128::from(i64::MAX) + 1).into_pyobject(py).unwrap();
+            assert!(offset_datetime_from_parquet_micros(&too_large).is_err());
+        })
+    }
+
+    #[test]
+    fn test_frompyobject_extra_nanoseconds() {
+        Python::with_gil(|py| {
+            let globals = [("datetime", py.import("datetime").unwrap())]
+                .into_py_dict(py)
+                .unwrap();
+            // Mimics pandas.Timestamp, which carries nanoseconds beyond the microsecond
+            py.run(
+                ffi::c_str!(
+                    r#"
+class Timestamp(datetime.datetime):
+    nanosecond = 345
+
+class NanoTime(datetime.time):
+    nanosecond = 345
+
+class BadTimestamp(datetime.datetime):
+    nanosecond = 1000
+
+tz = datetime.timezone(datetime.timedelta(hours=2))
+"#
+                ),
+                Some(&globals),
+                None,
+            )
+            .unwrap();
+            let eval = |code: &str| {
+                py.eval(&std::ffi::CString::new(code).unwrap(), Some(&globals), None)
+                    .unwrap()
+            };
+            let date = Date::from_calendar_date(2021, Month::June, 9).unwrap();
+            let time = Time::from_hms_nano(12, 34, 56, 789_012_345).unwrap();
+            let offset = UtcOffset::from_hms(2, 0, 0).unwrap();
+
+            assert_eq!(
+                eval("NanoTime(12, 34, 56, 789012)")
+                    .extract::<Time>()
+                    .unwrap(),
+                time
+            );
+            assert_eq!(
+                eval("Timestamp(2021, 6, 9, 12, 34, 56, 789012)")
+                    .extract::<PrimitiveDateTime>()
+                    .unwrap(),
+                PrimitiveDateTime::new(date, time)
+            );
+            assert_eq!(
+                eval("Timestamp(2021, 6, 9, 12, 34, 56, 789012, tz)")
+                    .extract::<OffsetDateTime>()
+                    .unwrap(),
+                PrimitiveDateTime::new(date, time).assume_offset(offset)
+            );
+
+            // Plain datetimes keep microsecond precision
+            assert_eq!(
+                eval("datetime.datetime(2021, 6, 9, 12, 34, 56, 789012)")
+                    .extract::<PrimitiveDateTime>()
+                    .unwrap(),
+                PrimitiveDateTime::new(date, time.replace_nanosecond(789_012_000).unwrap())
+            );
+
+            assert_eq!(
+                eval("BadTimestamp(2021, 6, 9)")
+                    .extract::<PrimitiveDateTime>()
+                    .unwrap_err()
+                    .to_string(),
+                "ValueError: nanosecond must be in 0..=999, got 1000"
+            );
+        })
+    }
+
+    #[test]
+    fn test_duration_to_pystr() {
+        Python::with_gil(|py| {
+            for duration in [
+                Duration::ZERO,
+                Duration::microseconds(5),
+                -Duration::microseconds(5),
+                Duration::seconds(-1),
+                Duration::hours(10) + Duration::seconds(1),
+                Duration::days(1),
+                -Duration::days(1),
+                Duration::days(1)
+                    + Duration::hours(2)
+                    + Duration::minutes(3)
+                    + Duration::seconds(4)
+                    + Duration::microseconds(5),
+                Duration::days(2) + Duration::nanoseconds(999),
+                -Duration::days(2) - Duration::milliseconds(1),
+                Duration::days(999_999_999)
+                    + Duration::seconds(86_399)
+                    + Duration::microseconds(999_999),
+                -Duration::days(999_999_999),
+            ] {
+                let expected = duration.into_pyobject(py).unwrap().str().unwrap();
+                assert_eq!(
+                    duration_to_pystr(py, duration).unwrap().to_string(),
+                    expected.to_string(),
+                    "{}",
+                    duration
+                );
+            }
+
+            let err = duration_to_pystr(py, Duration::days(1_000_000_000)).unwrap_err();
+            assert!(err.is_instance_of::<PyOverflowError>(py));
+        })
+    }
+
+    #[test]
+    // Only Python>=3.9 has the zoneinfo package
+    // We skip the test on windows too since we'd need to install
+    // tzdata there to make this work.
+    #[cfg(all(Py_3_9, not(target_os = "windows")))]
+    fn test_offset_datetime_extract_with_gap_flag() {
+        Python::with_gil(|py| {
+            let zone = py
+                .import("zoneinfo")
+                .unwrap()
+                .getattr("ZoneInfo")
+                .unwrap()
+                .call1(("Europe/London",))
+                .unwrap();
+            // London springs forward from 01:00 GMT to 02:00 BST on 2021-03-28
+            let check = |hour, minute, fold: u8, offset_hours, in_gap| {
+                let kwargs = [("fold", fold)].into_py_dict(py).unwrap();
+                let py_datetime = py
+                    .import("datetime")
+                    .unwrap()
+                    .getattr("datetime")
+                    .unwrap()
+                    .call((2021, 3, 28, hour, minute, 0, 0, &zone), Some(&kwargs))
+                    .unwrap();
+                let (datetime, gap) = offset_datetime_extract_with_gap_flag(&py_datetime).unwrap();
+                assert_eq!(gap, in_gap, "{:02}:{:02} fold={}", hour, minute, fold);
+                assert_eq!(
+                    datetime,
+                    Date::from_calendar_date(2021, Month::March, 28)
+                        .unwrap()
+                        .with_hms(hour, minute, 0)
+                        .unwrap()
+                        .assume_offset(UtcOffset::from_hms(offset_hours, 0, 0).unwrap())
+                );
+                // The lenient default extraction agrees
+                assert_eq!(py_datetime.extract::<OffsetDateTime>().unwrap(), datetime);
+            };
+            check(0, 59, 0, 0, false);
+            check(1, 0, 0, 0, true);
+            check(1, 30, 0, 0, true);
+            check(1, 30, 1, 1, true);
+            check(2, 0, 0, 1, false);
+
+            // Fixed offsets never have gaps
+            let datetime = Date::from_calendar_date(2021, Month::March, 28)
+                .unwrap()
+                .with_hms(1, 30, 0)
+                .unwrap()
+                .assume_utc();
+            let py_datetime = datetime.into_pyobject(py).unwrap();
+            assert_eq!(
+                offset_datetime_extract_with_gap_flag(&py_datetime).unwrap(),
+                (datetime, false)
+            );
+
+            // Times near the range edges, whose UTC equivalent Python can't represent
+            for (date, hour, offset_hours) in [
+                (
+                    Date::from_calendar_date(9999, Month::December, 31).unwrap(),
+                    23,
+                    -5,
+                ),
+                (
+                    Date::from_calendar_date(1, Month::January, 1).unwrap(),
+                    0,
+                    5,
+                ),
+            ] {
+                let offset = UtcOffset::from_hms(offset_hours, 0, 0).unwrap();
+                let datetime = date.with_hms(hour, 0, 0).unwrap().assume_offset(offset);
+                let py_datetime = new_py_datetime_ob(
+                    py,
+                    "datetime",
+                    (
+                        date.year(),
+                        u8::from(date.month()),
+                        date.day(),
+                        hour,
+                        0,
+                        0,
+                        0,
+                        offset.into_pyobject(py).unwrap(),
+                    ),
+                );
+                assert_eq!(
+                    offset_datetime_extract_with_gap_flag(&py_datetime).unwrap(),
+                    (datetime, false)
+                );
+            }
+        })
+    }
+
+    #[test]
+    // Only Python>=3.9 has the zoneinfo package
+    // We skip the test on windows too since we'd need to install
+    // tzdata there to make this work.
+    #[cfg(all(Py_3_9, not(target_os = "windows")))]
+    fn test_offset_datetime_to_struct_time() {
+        Python::with_gil(|py| {
+            let zone = zoneinfo_from_key(py, "Europe/London").unwrap();
+            let summer = Date::from_calendar_date(2021, Month::July, 1)
+                .unwrap()
+                .with_hms(12, 30, 15)
+                .unwrap()
+                .assume_offset(UtcOffset::from_hms(1, 0, 0).unwrap());
+            let winter = Date::from_calendar_date(2021, Month::December, 31)
+                .unwrap()
+                .with_hms(23, 59, 59)
+                .unwrap()
+                .assume_utc();
+            let fixed = timezone_utc(py);
+
+            let cases = [
+                (summer, None, (2021, 7, 1, 12, 30, 15, 3, 182, -1)),
+                (summer, Some(&zone), (2021, 7, 1, 12, 30, 15, 3, 182, 1)),
+                (winter, None, (2021, 12, 31, 23, 59, 59, 4, 365, -1)),
+                (winter, Some(&zone), (2021, 12, 31, 23, 59, 59, 4, 365, 0)),
+                // A fixed offset has no DST information
+                (summer, Some(&fixed), (2021, 7, 1, 12, 30, 15, 3, 182, -1)),
+            ];
+            for (dt, tzinfo, expected) in cases {
+                let struct_time = offset_datetime_to_struct_time(py, &dt, tzinfo).unwrap();
+                assert_eq!(
+                    struct_time
+                        .extract::<(i32, u8, u8, u8, u8, u8, u8, u16, i32)>()
+                        .unwrap(),
+                    expected,
+                    "{} with {:?}",
+                    dt,
+                    tzinfo
+                );
+                assert_eq!(
+                    struct_time
+                        .getattr("tm_isdst")
+                        .unwrap()
+                        .extract::<i32>()
+                        .unwrap(),
+                    expected.8
+                );
+            }
+
+            // Without a tzinfo the result matches Python's `timetuple()`
+            let timetuple = summer
+                .into_pyobject(py)
+                .unwrap()
+                .call_method0("timetuple")
+                .unwrap();
+            assert!(offset_datetime_to_struct_time(py, &summer, None)
+                .unwrap()
+                .eq(timetuple)
+                .unwrap());
+        })
+    }
+
+    #[test]
+    fn test_common_zone_into_pyobject() {
+        Python::with_gil(|py| {
+            // The policy for UtcOffset conversions doesn't apply
+            let previous = set_tz_repr_policy(TzReprPolicy::PreferZoneInfoKey);
+            let utc = CommonZone::Utc.into_pyobject(py).unwrap();
+            assert!(utc.is(&timezone_utc(py)));
+
+            let offset = UtcOffset::from_hms(5, 30, 0).unwrap();
+            let fixed = CommonZone::FixedOffset(offset).into_pyobject(py).unwrap();
+            let timezone = py.import("datetime").unwrap().getattr("timezone").unwrap();
+            assert!(fixed.get_type().is(&timezone));
+            assert_eq!(fixed.extract::<UtcOffset>().unwrap(), offset);
+            // A zero fixed offset still gives a fixed offset timezone rather than a ZoneInfo
+            let zero = (&CommonZone::FixedOffset(UtcOffset::UTC))
+                .into_pyobject(py)
+                .unwrap();
+            assert!(zero.get_type().is(&timezone));
+            set_tz_repr_policy(previous);
+        })
+    }
+
+    #[test]
+    // Only Python>=3.9 has the zoneinfo package
+    // We skip the test on windows too since we'd need to install
+    // tzdata there to make this work.
+    #[cfg(all(Py_3_9, not(target_os = "windows")))]
+    fn test_common_zone_named_into_pyobject() {
+        Python::with_gil(|py| {
+            let zone = CommonZone::Named("Europe/London")
+                .into_pyobject(py)
+                .unwrap();
+            assert!(zone.is(&zoneinfo_from_key(py, "Europe/London").unwrap()));
+
+            let err = CommonZone::Named("Bogus/Zone")
+                .into_pyobject(py)
+                .unwrap_err();
+            assert_eq!(
+                err.to_string(),
+                "ValueError: unknown time zone \"Bogus/Zone\""
+            );
+        })
+    }
+
+    #[test]
+    fn test_primitive_datetime_into_pyobject_is_naive() {
+        Python::with_gil(|py| {
+            let datetime = Date::from_calendar_date(2021, Month::June, 9)
+                .unwrap()
+                .with_hms_micro(12, 34, 56, 789)
+                .unwrap();
+            // Policies for aware conversions must not attach a zone to a naive datetime
+            for policy in [
+                TzReprPolicy::FixedOffset,
+                TzReprPolicy::PreferUtcSingleton,
+                TzReprPolicy::PreferZoneInfoKey,
+            ] {
+                let previous = set_tz_repr_policy(policy);
+                let py_datetime = datetime.into_pyobject(py).unwrap();
+                set_tz_repr_policy(previous);
+                assert!(py_datetime.getattr("tzinfo").unwrap().is_none());
+                assert!(py_datetime.call_method0("utcoffset").unwrap().is_none());
+                assert_eq!(
+                    py_datetime.extract::<PrimitiveDateTime>().unwrap(),
+                    datetime
+                );
+                // And so it is never taken for an aware datetime
+                assert!(py_datetime.extract::<OffsetDateTime>().is_err());
+            }
+        })
+    }
+
+    #[test]
+    fn test_duration_py_nanos_roundtrip() {
+        Python::with_gil(|py| {
+            for duration in [
+                Duration::ZERO,
+                Duration::nanoseconds(1),
+                Duration::nanoseconds(-1),
+                Duration::new(-5, -123_456_789),
+                Duration::new(86_400, 999_999_999),
+                Duration::MAX,
+                Duration::MIN,
+            ] {
+                let nanos = duration_to_py_nanos_i128(py, duration).unwrap();
+                assert_eq!(
+                    nanos.extract::<i128>().unwrap(),
+                    duration.whole_nanoseconds()
+                );
+                assert_eq!(duration_from_py_nanos(&nanos).unwrap(), duration);
+            }
+
+            // Python ints are unbounded, so Duration::MAX nanoseconds are exact
+            let max = duration_to_py_nanos_i128(py, Duration::MAX).unwrap();
+            let expected = py
+                .eval(
+                    ffi::c_str!("9223372036854775807 * 10**9 + 999999999"),
+                    None,
+                    None,
+                )
+                .unwrap();
+            assert!(max.as_any().eq(expected).unwrap());
+
+            let beyond = (Duration::MAX.whole_nanoseconds() + 1_000_000_000)
+                .into_pyobject(py)
+                .unwrap();
+            let err = duration_from_py_nanos(&beyond).unwrap_err();
+            assert!(err.is_instance_of::<PyOverflowError>(py));
+            let huge = py.eval(ffi::c_str!("10**40"), None, None).unwrap();
+            assert!(duration_from_py_nanos(&huge)
+                .unwrap_err()
+                .is_instance_of::<PyOverflowError>(py));
+        })
+    }
+
+    #[test]
+    fn test_offset_datetime_extract_trusted() {
+        Python::with_gil(|py| {
+            let globals = [("datetime", py.import("datetime").unwrap())]
+                .into_py_dict(py)
+                .unwrap();
+            py.run(
+                ffi::c_str!(
+                    r#"
+called = []
+
+class Custom(datetime.tzinfo):
+    def utcoffset(self, dt):
+        called.append("utcoffset")
+        return datetime.timedelta(hours=1)
+
+class Sub(datetime.datetime):
+    def utcoffset(self):
+        called.append("utcoffset")
+        return datetime.timedelta(hours=1)
+
+fixed = datetime.datetime(2021, 6, 9, 12, tzinfo=datetime.timezone(datetime.timedelta(hours=2)))
+custom = datetime.datetime(2021, 6, 9, 12, tzinfo=Custom())
+sub = Sub(2021, 6, 9, 12, tzinfo=datetime.timezone.utc)
+naive = datetime.datetime(2021, 6, 9, 12)
+"#
+                ),
+                Some(&globals),
+                None,
+            )
+            .unwrap();
+            let get = |name: &str| globals.get_item(name).unwrap().unwrap();
+
+            assert_eq!(
+                offset_datetime_extract_trusted(&get("fixed")).unwrap(),
+                Date::from_calendar_date(2021, Month::June, 9)
+                    .unwrap()
+                    .with_hms(12, 0, 0)
+                    .unwrap()
+                    .assume_offset(UtcOffset::from_hms(2, 0, 0).unwrap())
+            );
+
+            let err = offset_datetime_extract_trusted(&get("custom")).unwrap_err();
+            assert!(err.is_instance_of::<PyTypeError>(py));
+            assert_eq!(
+                err.value(py).to_string(),
+                "untrusted tzinfo type <class 'Custom'>, expected datetime.timezone or zoneinfo.ZoneInfo"
+            );
+            let err = offset_datetime_extract_trusted(&get("sub")).unwrap_err();
+            assert_eq!(
+                err.value(py).to_string(),
+                "expected exactly datetime.datetime, got <class 'Sub'>"
+            );
+            // Neither utcoffset() was run
+            assert_eq!(get("called").len().unwrap(), 0);
+
+            assert!(offset_datetime_extract_trusted(&get("naive"))
+                .unwrap_err()
+                .is_instance_of::<PyTypeError>(py));
+        })
+    }
+
+    #[test]
+    // Only Python>=3.9 has the zoneinfo package
+    // We skip the test on windows too since we'd need to install
+    // tzdata there to make this work.
+    #[cfg(all(Py_3_9, not(target_os = "windows")))]
+    fn test_offset_datetime_extract_trusted_zoneinfo() {
+        Python::with_gil(|py| {
+            let zone = zoneinfo_from_key(py, "Europe/London").unwrap();
+            let py_datetime = new_py_datetime_ob(py, "datetime", (2021, 6, 9, 12, 0, 0, 0, zone));
+            assert_eq!(
+                offset_datetime_extract_trusted(&py_datetime).unwrap(),
+                Date::from_calendar_date(2021, Month::June, 9)
+                    .unwrap()
+                    .with_hms(12, 0, 0)
+                    .unwrap()
+                    .assume_offset(UtcOffset::from_hms(1, 0, 0).unwrap())
+            );
+        })
+    }
+
+    #[test]
+    fn test_date_is_weekend_into_py() {
+        Python::with_gil(|py| {
+            // 2021-06-07 is a Monday
+            let monday = Date::from_calendar_date(2021, Month::June, 7).unwrap();
+            for offset in 0..7 {
+                let date = monday + Duration::days(offset);
+                let is_weekend = date_is_weekend_into_py(py, date).unwrap();
+                let weekday: u8 = date
+                    .into_pyobject(py)
+                    .unwrap()
+                    .call_method0("weekday")
+                    .unwrap()
+                    .extract()
+                    .unwrap();
+                assert_eq!(
+                    is_weekend.extract::<bool>().unwrap(),
+                    weekday >= 5,
+                    "{}",
+                    date
+                );
+            }
+        })
+    }
+
+    #[test]
+    fn test_date_add_business_days() {
+        Python::with_gil(|py| {
+            let is_weekend =
+                |date: Date| matches!(date.weekday(), Weekday::Saturday | Weekday::Sunday);
+            // Steps one day at a time
+            let reference = |mut date: Date, n: i64| {
+                for _ in 0..n.abs() {
+                    loop {
+                        date = if n > 0 {
+                            date.next_day().unwrap()
+                        } else {
+                            date.previous_day().unwrap()
+                        };
+                        if !is_weekend(date) {
+                            break;
+                        }
+                    }
+                }
+                date
+            };
+            // 2021-06-07 is a Monday
+            let monday = Date::from_calendar_date(2021, Month::June, 7).unwrap();
+            for start in 0..14 {
+                let date = monday + Duration::days(start);
+                for n in -23..=23 {
+                    let result: Date = date_add_business_days(py, date, n)
+                        .unwrap()
+                        .extract()
+                        .unwrap();
+                    assert_eq!(result, reference(date, n), "{} + {}", date, n);
+                }
+            }
+
+            let friday = Date::from_calendar_date(2021, Month::June, 11).unwrap();
+            let saturday = friday.next_day().unwrap();
+            let result = |date, n| -> Date {
+                date_add_business_days(py, date, n)
+                    .unwrap()
+                    .extract()
+                    .unwrap()
+            };
+            assert_eq!(result(friday, 1), friday + Duration::days(3));
+            assert_eq!(result(saturday, 1), friday + Duration::days(3));
+            assert_eq!(result(saturday, -1), friday);
+            assert_eq!(result(saturday, 0), saturday);
+            // 2021 has 261 business days
+            let new_year = Date::from_calendar_date(2021, Month::January, 1).unwrap();
+            assert_eq!(
+                result(new_year, 261),
+                Date::from_calendar_date(2022, Month::January, 3).unwrap()
+            );
+
+            for n in [i64::MAX, i64::MIN, 3_000_000, -3_000_000] {
+                let err = date_add_business_days(py, friday, n).unwrap_err();
+                assert!(err.is_instance_of::<PyOverflowError>(py), "{}", n);
+            }
+        })
+    }
+
+    #[test]
+    fn test_offset_datetime_time_ns() {
+        Python::with_gil(|py| {
+            let ns = 1_623_242_096_123_456_789i128;
+            let datetime = offset_datetime_from_time_ns(py, ns).unwrap();
+            assert_eq!(
+                datetime,
+                Date::from_calendar_date(2021, Month::June, 9)
+                    .unwrap()
+                    .with_hms_nano(12, 34, 56, 123_456_789)
+                    .unwrap()
+                    .assume_utc()
+            );
+            let py_ns = offset_datetime_to_time_ns(py, &datetime).unwrap();
+            assert_eq!(py_ns.extract::<i128>().unwrap(), ns);
+            // Going through a datetime loses the nanoseconds
+            let roundtripped: OffsetDateTime =
+                datetime.into_pyobject(py).unwrap().extract().unwrap();
+            assert_eq!(roundtripped.nanosecond(), 123_456_000);
+
+            // Timestamps before the epoch, and a value straight from Python
+            let before = offset_datetime_from_time_ns(py, -1).unwrap();
+            assert_eq!(
+                before,
+                Date::from_calendar_date(1969, Month::December, 31)
+                    .unwrap()
+                    .with_hms_nano(23, 59, 59, 999_999_999)
+                    .unwrap()
+                    .assume_utc()
+            );
+            let now: i128 = py
+                .import("time")
+                .unwrap()
+                .call_method0("time_ns")
+                .unwrap()
+                .extract()
+                .unwrap();
+            let datetime = offset_datetime_from_time_ns(py, now).unwrap();
+            assert_eq!(
+                offset_datetime_to_time_ns(py, &datetime)
+                    .unwrap()
+                    .extract::<i128>()
+                    .unwrap(),
+                now
+            );
+
+            let err = offset_datetime_from_time_ns(py, i128::MAX).unwrap_err();
+            assert!(err.is_instance_of::<PyValueError>(py));
+        })
+    }
+
+    #[test]
+    fn test_offset_datetime_to_named_outputs() {
+        Python::with_gil(|py| {
+            let td = Duration::minutes(330).into_pyobject(py).unwrap();
+            let tz = new_py_datetime_ob(py, "timezone", (td, "IST"));
+            #[cfg(not(Py_LIMITED_API))]
+            let tz = tz.downcast_into::<PyTzInfo>().unwrap();
+            let dt = Date::from_calendar_date(2021, Month::June, 9)
+                .unwrap()
+                .with_hms_nano(7, 4, 56, 123_456_789)
+                .unwrap()
+                .assume_utc();
+
+            let outputs = offset_datetime_to_named_outputs(py, &dt, &tz).unwrap();
+            assert_eq!(outputs.tzname.as_deref(), Some("IST"));
+            assert_eq!(
+                outputs.datetime.call_method0("tzname").unwrap().to_string(),
+                "IST"
+            );
+            assert_eq!(
+                outputs.datetime.extract::<OffsetDateTime>().unwrap(),
+                dt.to_offset(UtcOffset::from_hms(5, 30, 0).unwrap())
+                    .replace_nanosecond(123_456_000)
+                    .unwrap()
+            );
+            assert_eq!(
+                outputs.isoformat.to_string(),
+                "2021-06-09T12:34:56.123456+05:30"
+            );
+            let struct_time = &outputs.struct_time;
+            assert_eq!(struct_time.getattr("tm_zone").unwrap().to_string(), "IST");
+            assert_eq!(
+                struct_time
+                    .getattr("tm_gmtoff")
+                    .unwrap()
+                    .extract::<i32>()
+                    .unwrap(),
+                19800
+            );
+            assert_eq!(
+                struct_time
+                    .getattr("tm_hour")
+                    .unwrap()
+                    .extract::<u8>()
+                    .unwrap(),
+                12
+            );
+            // A fixed offset timezone has no DST information
+            assert_eq!(
+                struct_time
+                    .getattr("tm_isdst")
+                    .unwrap()
+                    .extract::<i32>()
+                    .unwrap(),
+                -1
+            );
+        })
+    }
+
+    #[test]
+    // Only Python>=3.9 has the zoneinfo package
+    // We skip the test on windows too since we'd need to install
+    // tzdata there to make this work.
+    #[cfg(all(Py_3_9, not(target_os = "windows")))]
+    fn test_offset_datetime_to_named_outputs_zoneinfo() {
+        Python::with_gil(|py| {
+            let zone = zoneinfo_from_key(py, "Europe/London").unwrap();
+            for (month, tzname, hour, isdst) in
+                [(Month::July, "BST", 13, 1), (Month::January, "GMT", 12, 0)]
+            {
+                let dt = Date::from_calendar_date(2021, month, 9)
+                    .unwrap()
+                    .with_hms(12, 0, 0)
+                    .unwrap()
+                    .assume_utc();
+                let outputs = offset_datetime_to_named_outputs(py, &dt, &zone).unwrap();
+                assert_eq!(outputs.tzname.as_deref(), Some(tzname));
+                assert_eq!(
+                    outputs.datetime.call_method0("tzname").unwrap().to_string(),
+                    tzname
+                );
+                let struct_time = &outputs.struct_time;
+                assert_eq!(struct_time.getattr("tm_zone").unwrap().to_string(), tzname);
+                assert_eq!(
+                    struct_time
+                        .getattr("tm_hour")
+                        .unwrap()
+                        .extract::<u8>()
+                        .unwrap(),
+                    hour
+                );
+                assert_eq!(
+                    struct_time
+                        .getattr("tm_isdst")
+                        .unwrap()
+                        .extract::<i32>()
+                        .unwrap(),
+                    isdst
+                );
+                assert!(outputs.isoformat.to_string().starts_with(&format!(
+                    "2021-{:02}-09T{}:00:00",
+                    u8::from(month),
+                    hour
+                )));
+            }
+        })
+    }
+
+    #[test]
+    fn test_date_iso_week_string() {
+        Python::with_gil(|py| {
+            let parse = |s: &str| date_from_iso_week_string(py, &PyString::new(py, s));
+            let cases = [
+                ("2021-W01-1", (2021, Month::January, 4)),
+                // 2020 is a long ISO year, 2020-W53-5 is New Year's Day 2021
+                ("2020-W53-5", (2021, Month::January, 1)),
+                ("2020-W01-1", (2019, Month::December, 30)),
+                ("2015-W53-7", (2016, Month::January, 3)),
+                ("0001-W01-1", (1, Month::January, 1)),
+            ];
+            for (iso, (year, month, day)) in cases {
+                let date = Date::from_calendar_date(year, month, day).unwrap();
+                assert_eq!(parse(iso).unwrap(), date, "{}", iso);
+                assert_eq!(date_to_iso_week_string(py, date).unwrap().to_string(), iso);
+                // Python agrees on the components
+                let (iso_year, week, weekday): (i32, u8, u8) = date
+                    .into_pyobject(py)
+                    .unwrap()
+                    .call_method0("isocalendar")
+                    .unwrap()
+                    .extract()
+                    .unwrap();
+                assert_eq!(format!("{:04}-W{:02}-{}", iso_year, week, weekday), iso);
+            }
+
+            let errors = [
+                // 2021 is a short ISO year
+                ("2021-W53-1", "ISO year 2021 has no week 53"),
+                ("2021-W00-1", "week 0 is out of range, must be in 1..=53"),
+                ("2021-W54-1", "week 54 is out of range, must be in 1..=53"),
+                ("2021-W01-0", "weekday 0 is out of range, must be in 1..=7"),
+                ("2021-W01-8", "weekday 8 is out of range, must be in 1..=7"),
+                ("2021-01-1", "invalid ISO week date \"2021-01-1\""),
+                ("2021-W1-01", "invalid ISO week date \"2021-W1-01\""),
+                ("2021-W+1-1", "invalid ISO week date \"2021-W+1-1\""),
+                ("2021W011", "invalid ISO week date \"2021W011\""),
+            ];
+            for (iso, message) in errors {
+                let err = parse(iso).unwrap_err();
+                assert!(err.is_instance_of::<PyValueError>(py));
+                assert_eq!(err.value(py).to_string(), message, "{}", iso);
+            }
+            // Year 0 and the days after 9999-12-31 don't exist in Python
+            assert!(parse("0000-W01-1").is_err());
+            assert!(parse("9999-W52-5").is_ok());
+            assert!(parse("9999-W52-7").is_err());
+        })
+    }
+
+    #[test]
+    fn test_extract_primitive_datetimes_from_index() {
+        Python::with_gil(|py| {
+            let globals = [("datetime", py.import("datetime").unwrap())]
+                .into_py_dict(py)
+                .unwrap();
+            // Stands in for pandas, which stores a DatetimeIndex as int64 nanoseconds
+            py.run(
+                ffi::c_str!(
+                    r#"
+import array
+
+class DatetimeIndex:
+    __module__ = "pandas.core.indexes.datetimes"
+
+    def __init__(self, nanos, tz=None, unit=None):
+        self.asi8 = array.array("q", nanos)
+        self.tz = tz
+        # Only pandas>=2.0 has non-nanosecond units
+        if unit is not None:
+            self.unit = unit
+
+    def __iter__(self):
+        raise AssertionError("a tz-naive index should not be iterated")
+"#
+                ),
+                Some(&globals),
+                None,
+            )
+            .unwrap();
+            let index_type = globals.get_item("DatetimeIndex").unwrap().unwrap();
+
+            let nanos = [
+                1_623_242_096_123_456_789i64,
+                0,
+                -1,
+                -9_223_372_036_854_775_807,
+            ];
+            let index = index_type.call1((nanos.to_vec(),)).unwrap();
+            let datetimes = extract_primitive_datetimes_from_index(&index).unwrap();
+            let expected: Vec<PrimitiveDateTime> = nanos
+                .iter()
+                .map(|&nanos| {
+                    let dt = OffsetDateTime::from_unix_timestamp_nanos(nanos.into()).unwrap();
+                    PrimitiveDateTime::new(dt.date(), dt.time())
+                })
+                .collect();
+            assert_eq!(datetimes, expected);
+            assert_eq!(datetimes[0].nanosecond(), 123_456_789);
+
+            let with_nat = index_type.call1((vec![0, i64::MIN],)).unwrap();
+            assert_eq!(
+                extract_primitive_datetimes_from_index(&with_nat)
+                    .unwrap_err()
+                    .value(py)
+                    .to_string(),
+                "NaT at position 1 cannot be converted to PrimitiveDateTime"
+            );
+
+            // The values are in the index's unit
+            let seconds = [1_623_242_096i64, -1, 9_000_000_000];
+            let expected: Vec<PrimitiveDateTime> = seconds
+                .iter()
+                .map(|&s| {
+                    let dt = OffsetDateTime::from_unix_timestamp(s).unwrap();
+                    PrimitiveDateTime::new(dt.date(), dt.time())
+                })
+                .collect();
+            for (unit, scale) in [
+                ("s", 1),
+                ("ms", 1_000),
+                ("us", 1_000_000),
+                ("ns", 1_000_000_000),
+            ] {
+                let values: Vec<i64> = seconds.iter().map(|s| s * scale).collect();
+                let kwargs = [("unit", unit)].into_py_dict(py).unwrap();
+                let index = index_type.call((values,), Some(&kwargs)).unwrap();
+                assert_eq!(
+                    extract_primitive_datetimes_from_index(&index).unwrap(),
+                    expected,
+                    "{}",
+                    unit
+                );
+            }
+            // Coarser units reach beyond the nanosecond range of about ±292 years
+            let kwargs = [("unit", "s")].into_py_dict(py).unwrap();
+            let index = index_type
+                .call((vec![253_402_300_799i64],), Some(&kwargs))
+                .unwrap();
+            assert_eq!(
+                extract_primitive_datetimes_from_index(&index).unwrap(),
+                vec![Date::from_calendar_date(9999, Month::December, 31)
+                    .unwrap()
+                    .with_hms(23, 59, 59)
+                    .unwrap()]
+            );
+            let kwargs = [("unit", "D")].into_py_dict(py).unwrap();
+            let index = index_type.call((vec![0i64],), Some(&kwargs)).unwrap();
+            assert_eq!(
+                extract_primitive_datetimes_from_index(&index)
+                    .unwrap_err()
+                    .value(py)
+                    .to_string(),
+                "unsupported DatetimeIndex unit \"D\""
+            );
+
+            // Other iterables are extracted element-wise
+            let datetime = Date::from_calendar_date(2021, Month::June, 9)
+                .unwrap()
+                .with_hms_micro(12, 34, 56, 789)
+                .unwrap();
+            let list = PyList::new(py, [datetime, datetime + Duration::days(1)]).unwrap();
+            assert_eq!(
+                extract_primitive_datetimes_from_index(&list).unwrap(),
+                vec![datetime, datetime + Duration::days(1)]
+            );
+            let aware = index_type.call1((vec![0i64], python_utc(py))).unwrap();
+            assert!(extract_primitive_datetimes_from_index(&aware)
+                .unwrap_err()
+                .to_string()
+                .contains("should not be iterated"));
+        })
+    }
+
+    #[test]
+    fn test_duration_into_pydelta_rounded() {
+        Python::with_gil(|py| {
+            let micros = |duration: Duration, mode| -> i64 {
+                duration_into_pydelta_rounded(py, duration, mode)
+                    .unwrap()
+                    .extract::<Duration>()
+                    .unwrap()
+                    .whole_microseconds() as i64
+            };
+            let cases = [
+                // nanoseconds, truncate, half even, half up, half down
+                (1500, 1, 2, 2, 1),
+                (2500, 2, 2, 3, 2),
+                (1499, 1, 1, 1, 1),
+                (1501, 1, 2, 2, 2),
+                (-1500, -1, -2, -2, -1),
+                (-2500, -2, -2, -3, -2),
+                (-1501, -1, -2, -2, -2),
+                (999, 0, 1, 1, 1),
+            ];
+            for (nanos, truncate, half_even, half_up, half_down) in cases {
+                let duration = Duration::nanoseconds(nanos);
+                assert_eq!(
+                    micros(duration, SubMicroMode::Truncate),
+                    truncate,
+                    "{}",
+                    nanos
+                );
+                assert_eq!(
+                    micros(duration, SubMicroMode::HalfEven),
+                    half_even,
+                    "{}",
+                    nanos
+                );
+                assert_eq!(micros(duration, SubMicroMode::HalfUp), half_up, "{}", nanos);
+                assert_eq!(
+                    micros(duration, SubMicroMode::HalfDown),
+                    half_down,
+                    "{}",
+                    nanos
+                );
+            }
+
+            // Truncating matches the regular conversion
+            let duration = Duration::new(-86_401, -123_456_789);
+            assert!(
+                duration_into_pydelta_rounded(py, duration, SubMicroMode::Truncate)
+                    .unwrap()
+                    .eq(duration.into_pyobject(py).unwrap())
+                    .unwrap()
+            );
+
+            // Rounding up carries into the seconds and days
+            let delta = duration_into_pydelta_rounded(
+                py,
+                Duration::new(86_399, 999_999_500),
+                SubMicroMode::HalfUp,
+            )
+            .unwrap();
+            assert!(delta
+                .eq(new_py_datetime_ob(py, "timedelta", (1, 0, 0)))
+                .unwrap());
+            let delta = duration_into_pydelta_rounded(
+                py,
+                Duration::new(-86_399, -999_999_500),
+                SubMicroMode::HalfEven,
+            )
+            .unwrap();
+            assert!(delta
+                .eq(new_py_datetime_ob(py, "timedelta", (-1, 0, 0)))
+                .unwrap());
+        })
+    }
+
+    #[test]
+    fn test_datetime_is_leap_year() {
+        Python::with_gil(|py| {
+            for (year, is_leap) in [(1900, false), (2000, true), (2020, true), (2021, false)] {
+                let date = new_py_datetime_ob(py, "date", (year, 3, 1));
+                assert_eq!(datetime_is_leap_year(&date).unwrap(), is_leap, "{}", year);
+                let datetime =
+                    new_py_datetime_ob(py, "datetime", (year, 3, 1, 12, 0, 0, 0, python_utc(py)));
+                assert_eq!(
+                    datetime_is_leap_year(&datetime).unwrap(),
+                    is_leap,
+                    "{}",
+                    year
+                );
+            }
+            let not_a_date = 2000i32.into_pyobject(py).unwrap();
+            assert!(datetime_is_leap_year(not_a_date.as_any()).is_err());
+        })
+    }
+
+    #[test]
+    fn test_utc_offset_into_pyobject_limits() {
+        Python::with_gil(|py| {
+            for offset in [
+                UtcOffset::from_hms(23, 59, 59).unwrap(),
+                UtcOffset::from_hms(-23, -59, -59).unwrap(),
+            ] {
+                let py_tz = offset.into_pyobject(py).unwrap();
+                assert_eq!(py_tz.extract::<UtcOffset>().unwrap(), offset);
+            }
+
+            for (offset, display) in [
+                (UtcOffset::from_hms(24, 0, 0).unwrap(), "+24:00:00"),
+                (UtcOffset::from_hms(-24, 0, 0).unwrap(), "-24:00:00"),
+                (UtcOffset::from_hms(25, 59, 59).unwrap(), "+25:59:59"),
+            ] {
+                let expected = format!(
+                    "ValueError: offset {} is out of range for Python's timezone: offset must be \
+                     strictly between -24:00 and +24:00",
+                    display
+                );
+                let err = offset.into_pyobject(py).unwrap_err();
+                assert_eq!(err.to_string(), expected);
+                let datetime = Date::from_calendar_date(2021, Month::June, 9)
+                    .unwrap()
+                    .midnight()
+                    .assume_offset(offset);
+                assert_eq!(
+                    datetime.into_pyobject(py).unwrap_err().to_string(),
+                    expected
+                );
+                assert_eq!(
+                    CommonZone::FixedOffset(offset)
+                        .into_pyobject(py)
+                        .unwrap_err()
+                        .to_string(),
+                    expected
+                );
+            }
+        })
+    }
+
+    #[test]
+    fn test_offset_datetime_from_unix_float() {
+        Python::with_gil(|py| {
+            let datetime = offset_datetime_from_unix_float(py, 1_609_459_200.123_456).unwrap();
+            assert_eq!(
+                datetime,
+                Date::from_calendar_date(2021, Month::January, 1)
+                    .unwrap()
+                    .with_hms_micro(0, 0, 0, 123_456)
+                    .unwrap()
+                    .assume_utc()
+            );
+
+            let utc = python_utc(py);
+            let fromtimestamp = py
+                .import("datetime")
+                .unwrap()
+                .getattr("datetime")
+                .unwrap()
+                .getattr("fromtimestamp")
+                .unwrap();
+            // A simple LCG gives reproducible timestamps across the year range
+            let mut state = 0x2545_f491_4f6c_dd1du64;
+            let mut next = || {
+                state = state
+                    .wrapping_mul(6_364_136_223_846_793_005)
+                    .wrapping_add(1_442_695_040_888_963_407);
+                state >> 11
+            };
+            for _ in 0..10_000 {
+                let secs = (next() % 310_000_000_000) as f64 - 60_000_000_000.0;
+                let ts = secs + (next() % 1_000_000_000) as f64 / 1e9;
+                let expected: OffsetDateTime =
+                    fromtimestamp.call1((ts, &utc)).unwrap().extract().unwrap();
+                assert_eq!(
+                    offset_datetime_from_unix_float(py, ts).unwrap(),
+                    expected,
+                    "{}",
+                    ts
+                );
+            }
+
+            assert!(offset_datetime_from_unix_float(py, f64::NAN).is_err());
+            assert!(offset_datetime_from_unix_float(py, 1e300).is_err());
+        })
+    }
+
+    #[test]
+    fn test_datetime_from_parts() {
+        Python::with_gil(|py| {
+            let date = Date::from_calendar_date(2021, Month::October, 31).unwrap();
+            let time = Time::from_hms_micro(1, 30, 0, 123_456).unwrap();
+            for fold in [0, 1] {
+                let naive = datetime_from_parts(py, date, time, None, fold).unwrap();
+                assert!(naive.getattr("tzinfo").unwrap().is_none());
+                assert_eq!(
+                    naive.getattr("fold").unwrap().extract::<u8>().unwrap(),
+                    fold
+                );
+                assert_eq!(
+                    naive.extract::<PrimitiveDateTime>().unwrap(),
+                    PrimitiveDateTime::new(date, time)
+                );
+            }
+
+            let tz = UtcOffset::from_hms(2, 0, 0)
+                .unwrap()
+                .into_pyobject(py)
+                .unwrap();
+            let aware = datetime_from_parts(py, date, time, Some(&tz), 1).unwrap();
+            assert!(aware.getattr("tzinfo").unwrap().is(&tz));
+            assert_eq!(aware.getattr("fold").unwrap().extract::<u8>().unwrap(), 1);
+
+            let err = datetime_from_parts(py, date, time, None, 2).unwrap_err();
+            assert_eq!(
+                err.to_string(),
+                "ValueError: fold must be either 0 or 1, got 2"
+            );
+        })
+    }
+
+    #[test]
+    // Only Python>=3.9 has the zoneinfo package
+    // We skip the test on windows too since we'd need to install
+    // tzdata there to make this work.
+    #[cfg(all(Py_3_9, not(target_os = "windows")))]
+    fn test_datetime_from_parts_zoneinfo_fold() {
+        Python::with_gil(|py| {
+            let zone = zoneinfo_from_key(py, "Europe/London").unwrap();
+            // 01:30 happens twice in London on 2021-10-31, first in BST and then in GMT
+            let date = Date::from_calendar_date(2021, Month::October, 31).unwrap();
+            let time = Time::from_hms(1, 30, 0).unwrap();
+            for (fold, offset_hours) in [(0, 1), (1, 0)] {
+                let datetime = datetime_from_parts(py, date, time, Some(&zone), fold).unwrap();
+                assert_eq!(
+                    datetime.extract::<OffsetDateTime>().unwrap(),
+                    PrimitiveDateTime::new(date, time)
+                        .assume_offset(UtcOffset::from_hms(offset_hours, 0, 0).unwrap())
+                );
+            }
+        })
+    }
+
+    #[test]
+    fn test_convert_offset_datetimes_with_stats() {
+        Python::with_gil(|py| {
+            let start = Date::from_calendar_date(2021, Month::June, 9)
+                .unwrap()
+                .with_hms(12, 34, 56)
+                .unwrap()
+                .assume_utc();
+            let datetimes = [
+                start,
+                start + Duration::microseconds(1),
+                start + Duration::nanoseconds(1),
+                start + Duration::nanoseconds(123_456_789),
+            ];
+            let (list, stats) = convert_offset_datetimes_with_stats(py, &datetimes).unwrap();
+            assert_eq!(
+                stats,
+                ConversionStats {
+                    converted: 4,
+                    truncated_leap_seconds: 0,
+                    truncated_sub_microseconds: 2,
+                }
+            );
+            assert_eq!(list.len(), 4);
+            for (py_datetime, datetime) in list.iter().zip(datetimes) {
+                assert!(py_datetime
+                    .eq(datetime
+                        .replace_nanosecond(datetime.microsecond() * 1000)
+                        .unwrap())
+                    .unwrap());
+            }
+
+            let (list, stats) = convert_offset_datetimes_with_stats(py, &[]).unwrap();
+            assert!(list.is_empty());
+            assert_eq!(stats, ConversionStats::default());
+        })
+    }
+
+    #[test]
+    fn test_date_from_py_isocalendar() {
+        Python::with_gil(|py| {
+            let fromisocalendar = py
+                .import("datetime")
+                .unwrap()
+                .getattr("date")
+                .unwrap()
+                .getattr("fromisocalendar")
+                .unwrap();
+            // The messages are CPython's, PyPy's pure Python `datetime` words some differently
+            let cases = [
+                (2021, 1, 1, None),
+                (2020, 53, 5, None),
+                (2015, 53, 7, None),
+                (2021, 53, 1, Some("Invalid week: 53")),
+                (2026, 53, 1, None),
+                (2021, 0, 1, Some("Invalid week: 0")),
+                (2021, 54, 1, Some("Invalid week: 54")),
+                (2021, 0, 9, Some("Invalid week: 0")),
+                (2021, 1, 0, Some("Invalid day: 0 (range is [1, 7])")),
+                (2021, 1, 8, Some("Invalid day: 8 (range is [1, 7])")),
+                (1, 1, 1, None),
+                (0, 1, 1, Some("Year is out of range: 0")),
+                (10000, 1, 1, Some("Year is out of range: 10000")),
+                (9999, 52, 5, None),
+                (9999, 52, 7, Some("year 10000 is out of range")),
+            ];
+            for (iso_year, week, weekday, message) in cases {
+                let expected = fromisocalendar.call1((iso_year, week, weekday));
+                let result = date_from_py_isocalendar(py, iso_year, week, weekday);
+                match (result, expected) {
+                    (Ok(date), Ok(expected)) => {
+                        assert!(message.is_none());
+                        assert_eq!(date, expected.extract::<Date>().unwrap())
+                    }
+                    (Err(err), Err(expected)) => {
+                        assert!(err.is_instance_of::<PyValueError>(py));
+                        assert!(expected.is_instance_of::<PyValueError>(py));
+                        assert_eq!(Some(err.value(py).to_string().as_str()), message);
+                    }
+                    (result, expected) => panic!(
+                        "{}-{}-{}: {:?} vs. Python's {:?}",
+                        iso_year, week, weekday, result, expected
+                    ),
+                }
+            }
+        })
+    }
+
+    #[test]
+    fn test_primitive_datetime_add_into_py() {
+        Python::with_gil(|py| {
+            let datetime = Date::from_calendar_date(2021, Month::June, 9)
+                .unwrap()
+                .with_hms_micro(12, 34, 56, 789)
+                .unwrap();
+            let duration = Duration::days(30) + Duration::microseconds(-1_000_001);
+            let sum = primitive_datetime_add_into_py(py, datetime, duration).unwrap();
+            assert_eq!(
+                sum.extract::<PrimitiveDateTime>().unwrap(),
+                datetime + duration
+            );
+            // Python agrees
+            let py_sum = datetime
+                .into_pyobject(py)
+                .unwrap()
+                .call_method1("__add__", (duration,))
+                .unwrap();
+            assert!(sum.eq(py_sum).unwrap());
+
+            let last = Date::from_calendar_date(9999, Month::December, 31)
+                .unwrap()
+                .with_hms(23, 59, 59)
+                .unwrap();
+            assert!(primitive_datetime_add_into_py(py, last, Duration::ZERO).is_ok());
+            let err = primitive_datetime_add_into_py(py, last, Duration::seconds(1)).unwrap_err();
+            assert!(err.is_instance_of::<PyOverflowError>(py));
+            assert_eq!(
+                err.value(py).to_string(),
+                "date value out of range: 9999-12-31 23:59:59.0 + 1s is outside of Python's years 1..=9999"
+            );
+            let first = Date::from_calendar_date(1, Month::January, 1)
+                .unwrap()
+                .midnight();
+            assert!(
+                primitive_datetime_add_into_py(py, first, Duration::nanoseconds(-1))
+                    .unwrap_err()
+                    .is_instance_of::<PyOverflowError>(py)
+            );
+            // Beyond the range of PrimitiveDateTime itself
+            assert!(primitive_datetime_add_into_py(py, last, Duration::MAX)
+                .unwrap_err()
+                .is_instance_of::<PyOverflowError>(py));
+        })
+    }
+
+    #[test]
+    fn test_month_calendar_consts() {
+        Python::with_gil(|py| {
+            let calendar = py.import("calendar").unwrap();
+            let names = [
+                "JANUARY",
+                "FEBRUARY",
+                "MARCH",
+                "APRIL",
+                "MAY",
+                "JUNE",
+                "JULY",
+                "AUGUST",
+                "SEPTEMBER",
+                "OCTOBER",
+                "NOVEMBER",
+                "DECEMBER",
+            ];
+            let mut month = Month::January;
+            for (i, name) in names.iter().enumerate() {
+                let number = i as u8 + 1;
+                let py_month = month_to_calendar_const(py, month).unwrap();
+                assert_eq!(py_month.extract::<u8>().unwrap(), number);
+                assert_eq!(month_from_calendar_const(&py_month).unwrap(), month);
+                // The constants were added in Python 3.12
+                if let Ok(constant) = calendar.getattr(*name) {
+                    assert_eq!(month_from_calendar_const(&constant).unwrap(), month);
+                    assert!(py_month.as_any().eq(constant).unwrap());
+                }
+                let expected = calendar
+                    .getattr("month_abbr")
+                    .unwrap()
+                    .get_item(number)
+                    .unwrap()
+                    .to_string();
+                assert_eq!(month_into_py_abbr(py, month).unwrap().to_string(), expected);
+                month = month.next();
+            }
+            assert_eq!(
+                month_into_py_abbr(py, Month::January).unwrap().to_string(),
+                "Jan"
+            );
+
+            for invalid in [0u8, 13] {
+                let ob = invalid.into_pyobject(py).unwrap();
+                assert!(month_from_calendar_const(&ob).is_err());
+            }
+        })
+    }
+
+    #[test]
+    fn test_duration_extract_exact() {
+        Python::with_gil(|py| {
+            let timedelta = py.import("datetime").unwrap().getattr("timedelta").unwrap();
+            let deltas = [
+                timedelta.getattr("max").unwrap(),
+                timedelta.getattr("min").unwrap(),
+                timedelta.getattr("resolution").unwrap(),
+                new_py_datetime_ob(py, "timedelta", (0, 0, -1)),
+                new_py_datetime_ob(py, "timedelta", (-1, 86_399, 999_999)),
+                new_py_datetime_ob(py, "timedelta", (123, 45_678, 901_234)),
+                new_py_datetime_ob(py, "timedelta", (0, 0, 0)),
+            ];
+            for delta in deltas {
+                let duration = duration_extract_exact(&delta).unwrap();
+                let back = duration.into_pyobject(py).unwrap();
+                assert!(back.eq(&delta).unwrap());
+                assert_eq!(
+                    back.repr().unwrap().to_string(),
+                    delta.repr().unwrap().to_string()
+                );
+            }
+            assert!(duration_extract_exact(&PyString::new(py, "1 day")).is_err());
+        })
+    }
+
+    #[test]
+    // Only Python>=3.9 has the zoneinfo package
+    // We skip the test on windows too since we'd need to install
+    // tzdata there to make this work.
+    #[cfg(all(Py_3_9, not(target_os = "windows")))]
+    fn test_offset_datetime_extract_with_resolver() {
+        Python::with_gil(|py| {
+            let zone = zoneinfo_from_key(py, "Europe/London").unwrap();
+            let kinds = std::cell::RefCell::new(Vec::new());
+            let force_one = |kind| {
+                kinds.borrow_mut().push(kind);
+                Fold::One
+            };
+            let expected = |month, day, hour, offset_hours| {
+                Date::from_calendar_date(2021, month, day)
+                    .unwrap()
+                    .with_hms(hour, 30, 0)
+                    .unwrap()
+                    .assume_offset(UtcOffset::from_hms(offset_hours, 0, 0).unwrap())
+            };
+
+            // 01:30 happens twice on 2021-10-31, the later one in GMT
+            let ambiguous = new_py_datetime_ob(py, "datetime", (2021, 10, 31, 1, 30, 0, 0, &zone));
+            assert_eq!(
+                ambiguous.extract::<OffsetDateTime>().unwrap(),
+                expected(Month::October, 31, 1, 1)
+            );
+            assert_eq!(
+                offset_datetime_extract_with_resolver(&ambiguous, force_one).unwrap(),
+                expected(Month::October, 31, 1, 0)
+            );
+            assert_eq!(
+                offset_datetime_extract_with_resolver(&ambiguous, |_| Fold::Zero).unwrap(),
+                expected(Month::October, 31, 1, 1)
+            );
+
+            // 01:30 doesn't exist on 2021-03-28, after the transition it would be in BST
+            let nonexistent = new_py_datetime_ob(py, "datetime", (2021, 3, 28, 1, 30, 0, 0, &zone));
+            assert_eq!(
+                offset_datetime_extract_with_resolver(&nonexistent, force_one).unwrap(),
+                expected(Month::March, 28, 1, 1)
+            );
+
+            // Other times don't need resolving
+            let regular = new_py_datetime_ob(py, "datetime", (2021, 6, 9, 12, 30, 0, 0, &zone));
+            assert_eq!(
+                offset_datetime_extract_with_resolver(&regular, force_one).unwrap(),
+                expected(Month::June, 9, 12, 1)
+            );
+            let fixed =
+                new_py_datetime_ob(py, "datetime", (2021, 10, 31, 1, 30, 0, 0, python_utc(py)));
+            assert_eq!(
+                offset_datetime_extract_with_resolver(&fixed, force_one).unwrap(),
+                expected(Month::October, 31, 1, 0)
+            );
+
+            assert_eq!(
+                *kinds.borrow(),
+                [AmbiguityKind::Ambiguous, AmbiguityKind::Nonexistent]
+            );
+        })
+    }
+
+    #[test]
+    fn test_format_with_format_description() {
+        Python::with_gil(|py| {
+            let date = Date::from_calendar_date(2021, Month::June, 9).unwrap();
+            let time = Time::from_hms_nano(7, 4, 5, 123_456_789).unwrap();
+            let datetime = PrimitiveDateTime::new(date, time);
+            let offset_datetime = datetime.assume_offset(UtcOffset::from_hms(-5, -30, 0).unwrap());
+
+            assert_eq!(
+                offset_datetime_format(
+                    py,
+                    &offset_datetime,
+                    "[year]-[month]-[day]T[hour]:[minute]:[second].[subsecond][offset_hour sign:mandatory]:[offset_minute]"
+                )
+                .unwrap()
+                .to_string(),
+                "2021-06-09T07:04:05.123456789-05:30"
+            );
+            assert_eq!(
+                primitive_datetime_format(
+                    py,
+                    datetime,
+                    "[weekday repr:short], [day padding:none] [month repr:long] [year] [hour repr:12]:[minute] [period case:lower]"
+                )
+                .unwrap()
+                .to_string(),
+                "Wed, 9 June 2021 07:04 am"
+            );
+            assert_eq!(
+                date_format(
+                    py,
+                    date,
+                    "[day].[month].[year repr:last_two] (day [ordinal])"
+                )
+                .unwrap()
+                .to_string(),
+                "09.06.21 (day 160)"
+            );
+            assert_eq!(
+                time_format(py, time, "[hour]h[minute]m[second]s [subsecond digits:3]ms")
+                    .unwrap()
+                    .to_string(),
+                "07h04m05s 123ms"
+            );
+
+            let err = date_format(py, date, "[year]-[nonsense]").unwrap_err();
+            assert!(err.is_instance_of::<PyValueError>(py));
+            assert!(err
+                .value(py)
+                .to_string()
+                .starts_with("invalid format description \"[year]-[nonsense]\": "));
+            // A component the value doesn't have
+            let err = primitive_datetime_format(py, datetime, "[offset_hour]").unwrap_err();
+            assert!(err.is_instance_of::<PyValueError>(py));
+            assert!(err
+                .value(py)
+                .to_string()
+                .starts_with("cannot format with \"[offset_hour]\": "));
+        })
+    }
+
+    #[test]
+    fn test_parse_with_format_description() {
+        Python::with_gil(|py| {
+            let date = Date::from_calendar_date(2021, Month::June, 9).unwrap();
+            let time = Time::from_hms_nano(7, 4, 5, 123_456_789).unwrap();
+            let datetime = PrimitiveDateTime::new(date, time);
+
+            assert_eq!(
+                offset_datetime_parse(
+                    py,
+                    &PyString::new(py, "2021-06-09T07:04:05.123456789-05:30"),
+                    "[year]-[month]-[day]T[hour]:[minute]:[second].[subsecond][offset_hour sign:mandatory]:[offset_minute]"
+                )
+                .unwrap(),
+                datetime.assume_offset(UtcOffset::from_hms(-5, -30, 0).unwrap())
+            );
+            assert_eq!(
+                primitive_datetime_parse(
+                    py,
+                    &PyString::new(py, "09/06/2021 07:04:05.123456789"),
+                    "[day]/[month]/[year] [hour]:[minute]:[second].[subsecond]"
+                )
+                .unwrap(),
+                datetime
+            );
+            assert_eq!(
+                date_parse(py, &PyString::new(py, "2021-160"), "[year]-[ordinal]").unwrap(),
+                date
+            );
+            assert_eq!(
+                time_parse(
+                    py,
+                    &PyString::new(py, "07h04m05s"),
+                    "[hour]h[minute]m[second]s"
+                )
+                .unwrap(),
+                Time::from_hms(7, 4, 5).unwrap()
+            );
+
+            // Round trip with formatting
+            let fmt = "[weekday], [day] [month repr:short] [year] [hour]:[minute]:[second]";
+            let formatted =
+                primitive_datetime_format(py, datetime.replace_nanosecond(0).unwrap(), fmt)
+                    .unwrap();
+            assert_eq!(
+                primitive_datetime_parse(py, &formatted, fmt).unwrap(),
+                datetime.replace_nanosecond(0).unwrap()
+            );
+
+            // Input not matching the format description
+            let err = date_parse(py, &PyString::new(py, "2021/06/09"), "[year]-[month]-[day]")
+                .unwrap_err();
+            assert!(err.is_instance_of::<PyValueError>(py));
+            assert!(err
+                .value(py)
+                .to_string()
+                .starts_with("cannot parse \"2021/06/09\" with \"[year]-[month]-[day]\": "));
+            // Out of range component
+            let err = time_parse(py, &PyString::new(py, "25:00"), "[hour]:[minute]").unwrap_err();
+            assert!(err.is_instance_of::<PyValueError>(py));
+            // Missing offset for an OffsetDateTime
+            let err = offset_datetime_parse(
+                py,
+                &PyString::new(py, "2021-06-09 07:04"),
+                "[year]-[month]-[day] [hour]:[minute]",
+            )
+            .unwrap_err();
+            assert!(err.is_instance_of::<PyValueError>(py));
+            // Invalid format description
+            let err = date_parse(py, &PyString::new(py, "2021"), "[year").unwrap_err();
+            assert!(err.is_instance_of::<PyValueError>(py));
+            assert!(err
+                .value(py)
+                .to_string()
+                .starts_with("invalid format description \"[year\": "));
+        })
+    }
+
+    #[test]
+    fn test_offset_datetime_utc_round_trip_keeps_utc_singleton() {
+        Python::with_gil(|py| {
+            let utc = python_utc(py);
+            let py_datetime = new_py_datetime_ob(py, "datetime", (2021, 6, 9, 12, 34, 56, 0, &utc));
+            let datetime: OffsetDateTime = py_datetime.extract().unwrap();
+            assert_eq!(datetime.offset(), UtcOffset::UTC);
+
+            let round_tripped = datetime.into_pyobject(py).unwrap();
+            assert!(round_tripped.getattr("tzinfo").unwrap().is(&utc));
+            assert!(round_tripped.eq(&py_datetime).unwrap());
+        })
+    }
+
+    #[test]
+    fn test_durations_to_timedelta_index() {
+        Python::with_gil(|py| {
+            // Stands in for pandas, which reads int64 nanoseconds from the buffer
+            let globals = PyDict::new(py);
+            py.run(
+                ffi::c_str!(
+                    r#"
+class TimedeltaIndex:
+    def __init__(self, data):
+        assert data.typecode == "q"
+        self.asi8 = list(data)
+"#
+                ),
+                Some(&globals),
+                None,
+            )
+            .unwrap();
+            let timedelta_index = globals.get_item("TimedeltaIndex").unwrap().unwrap();
+
+            let durations = [
+                Duration::new(86_400 * 365, 123_456_789),
+                Duration::ZERO,
+                Duration::nanoseconds(-1),
+                Duration::nanoseconds(i64::MAX),
+                Duration::nanoseconds(i64::MIN + 1),
+            ];
+            let index = durations_to_timedelta_index_with(&timedelta_index, &durations).unwrap();
+            let nanos: Vec<i64> = index.getattr("asi8").unwrap().extract().unwrap();
+            let expected: Vec<i64> = durations
+                .iter()
+                .map(|d| d.whole_nanoseconds() as i64)
+                .collect();
+            assert_eq!(nanos, expected);
+
+            let err = durations_to_timedelta_index_with(
+                &timedelta_index,
+                &[Duration::ZERO, Duration::nanoseconds(i64::MIN)],
+            )
+            .unwrap_err();
+            assert!(err.is_instance_of::<PyOverflowError>(py));
+            assert!(err
+                .value(py)
+                .to_string()
+                .ends_with(" at position 1 is out of range for a pandas TimedeltaIndex"));
+            assert!(durations_to_timedelta_index_with(
+                &timedelta_index,
+                &[Duration::days(365 * 300)]
+            )
+            .is_err());
+
+            // pandas is optional, so only test against it where it's installed
+            let pandas = match py.import("pandas") {
+                Ok(pandas) => pandas,
+                Err(_) => {
+                    let err = durations_to_timedelta_index(py, &durations).unwrap_err();
+                    assert!(err.is_instance_of::<PyImportError>(py));
+                    return;
+                }
+            };
+            let index = durations_to_timedelta_index(py, &durations).unwrap();
+            assert!(index
+                .is_instance(&pandas.getattr("TimedeltaIndex").unwrap())
+                .unwrap());
+            let nanos: Vec<i64> = index
+                .getattr("asi8")
+                .unwrap()
+                .call_method0("tolist")
+                .unwrap()
+                .extract()
+                .unwrap();
+            assert_eq!(nanos, expected);
+            let timedelta = pandas.getattr("Timedelta").unwrap();
+            let kwargs = [("nanoseconds", expected[0])].into_py_dict(py).unwrap();
+            assert!(index
+                .get_item(0)
+                .unwrap()
+                .eq(timedelta.call((), Some(&kwargs)).unwrap())
+                .unwrap());
+        })
+    }
+
+    #[test]
+    fn test_validate_tzinfo_consistency() {
+        Python::with_gil(|py| {
+            let summer = Date::from_calendar_date(2021, Month::June, 9)
+                .unwrap()
+                .with_hms_nano(12, 34, 56, 123_456_789)
+                .unwrap()
+                .assume_utc();
+            let winter = summer - Duration::days(150);
+
+            for offset in [UtcOffset::UTC, UtcOffset::from_hms(5, 30, 0).unwrap()] {
+                let tz = offset.into_pyobject(py).unwrap();
+                assert!(validate_tzinfo_consistency(tz.as_any(), &summer).unwrap());
+                assert!(validate_tzinfo_consistency(tz.as_any(), &winter).unwrap());
+            }
+
+            let globals = [("datetime", py.import("datetime").unwrap())]
+                .into_py_dict(py)
+                .unwrap();
+            py.run(
+                ffi::c_str!(
+                    r#"
+from datetime import timedelta, tzinfo
+
+class Consistent(tzinfo):
+    """UTC+1, with an additional hour of DST from April to September."""
+    def dst(self, dt):
+        return timedelta(hours=1 if 4 <= dt.month <= 9 else 0)
+    def utcoffset(self, dt):
+        return timedelta(hours=1) + self.dst(dt)
+
+class MissingDst(Consistent):
+    """Reports DST without including it in the offset."""
+    def utcoffset(self, dt):
+        return timedelta(hours=1)
+
+class BadFromUtc(Consistent):
+    """Converts from UTC with a different offset than it reports."""
+    def fromutc(self, dt):
+        return dt + timedelta(hours=3)
+
+class Naive(tzinfo):
+    def utcoffset(self, dt):
+        return None
+"#
+                ),
+                Some(&globals),
+                None,
+            )
+            .unwrap();
+            let tz = |name: &str| globals.get_item(name).unwrap().unwrap().call0().unwrap();
+
+            assert!(validate_tzinfo_consistency(&tz("Consistent"), &summer).unwrap());
+            assert!(validate_tzinfo_consistency(&tz("Consistent"), &winter).unwrap());
+            // The derived standard offset of UTC+0 in summer doesn't match the winter offset
+            assert!(!validate_tzinfo_consistency(&tz("MissingDst"), &summer).unwrap());
+            assert!(!validate_tzinfo_consistency(&tz("BadFromUtc"), &summer).unwrap());
+            assert!(!validate_tzinfo_consistency(&tz("BadFromUtc"), &winter).unwrap());
+            assert!(!validate_tzinfo_consistency(&tz("Naive"), &summer).unwrap());
+        })
+    }
+
+    #[test]
+    // Only Python>=3.9 has the zoneinfo package
+    // We skip the test on windows too since we'd need to install
+    // tzdata there to make this work.
+    #[cfg(all(Py_3_9, not(target_os = "windows")))]
+    fn test_validate_tzinfo_consistency_zoneinfo() {
+        Python::with_gil(|py| {
+            let zoneinfo = zoneinfo_from_key(py, "Europe/London").unwrap();
+            for month in [Month::January, Month::March, Month::June, Month::October] {
+                let at = Date::from_calendar_date(2021, month, 28)
+                    .unwrap()
+                    .midnight()
+                    .assume_utc();
+                assert!(
+                    validate_tzinfo_consistency(zoneinfo.as_any(), &at).unwrap(),
+                    "{}",
+                    at
+                );
+            }
+        })
+    }
+
+    #[test]
+    fn test_offset_datetime_with_nanos_round_trip() {
+        Python::with_gil(|py| {
+            let offset = UtcOffset::from_hms(-5, -30, 0).unwrap();
+            let date = Date::from_calendar_date(2021, Month::June, 9).unwrap();
+            for nanos in [0, 1, 999, 1_000, 123_456_789, 999_999_999] {
+                let datetime = date
+                    .with_hms_nano(12, 34, 56, nanos)
+                    .unwrap()
+                    .assume_offset(offset);
+                let tuple = offset_datetime_into_py_with_nanos(py, &datetime).unwrap();
+                let (py_datetime, extra_nanos): (Bound<'_, PyAny>, u32) = tuple.extract().unwrap();
+                assert_eq!(extra_nanos, nanos % 1000);
+                assert_eq!(
+                    py_datetime
+                        .getattr("microsecond")
+                        .unwrap()
+                        .extract::<u32>()
+                        .unwrap(),
+                    nanos / 1000
+                );
+                assert_eq!(
+                    offset_datetime_from_py_with_nanos(tuple.as_any()).unwrap(),
+                    datetime
+                );
+            }
+
+            // The extra nanoseconds replace the ones of a datetime carrying its own
+            let globals = [("datetime", py.import("datetime").unwrap())]
+                .into_py_dict(py)
+                .unwrap();
+            py.run(
+                ffi::c_str!(
+                    r#"
+class Timestamp(datetime.datetime):
+    nanosecond = 500
+
+ts = Timestamp(2021, 6, 9, 12, 34, 56, 999999, tzinfo=datetime.timezone.utc)
+"#
+                ),
+                Some(&globals),
+                None,
+            )
+            .unwrap();
+            let ts = globals.get_item("ts").unwrap().unwrap();
+            let expected = date
+                .with_hms_nano(12, 34, 56, 999_999_600)
+                .unwrap()
+                .assume_utc();
+            assert_eq!(
+                offset_datetime_from_py_with_nanos((&ts, 600).into_pyobject(py).unwrap().as_any())
+                    .unwrap(),
+                expected
+            );
+            assert_eq!(
+                offset_datetime_from_py_with_nanos((&ts, 0).into_pyobject(py).unwrap().as_any())
+                    .unwrap(),
+                expected.replace_nanosecond(999_999_000).unwrap()
+            );
+
+            let datetime = OffsetDateTime::UNIX_EPOCH.into_pyobject(py).unwrap();
+            for extra_nanos in [-1, 1000] {
+                let err = offset_datetime_from_py_with_nanos(
+                    (&datetime, extra_nanos).into_pyobject(py).unwrap().as_any(),
+                )
+                .unwrap_err();
+                assert!(err.is_instance_of::<PyValueError>(py));
+                assert_eq!(
+                    err.value(py).to_string(),
+                    format!("extra_nanos must be in 0..=999, got {}", extra_nanos)
+                );
+            }
+        })
+    }
+
+    #[test]
+    fn test_date_min_max_boundaries() {
+        Python::with_gil(|py| {
+            let date = py.import("datetime").unwrap().getattr("date").unwrap();
+            let (py_min, py_max) = (date.getattr("min").unwrap(), date.getattr("max").unwrap());
+            let min = Date::from_calendar_date(1, Month::January, 1).unwrap();
+            let max = Date::from_calendar_date(9999, Month::December, 31).unwrap();
+
+            assert_eq!(py_min.extract::<Date>().unwrap(), min);
+            assert_eq!(py_max.extract::<Date>().unwrap(), max);
+            assert!(min.into_pyobject(py).unwrap().eq(&py_min).unwrap());
+            assert!(max.into_pyobject(py).unwrap().eq(&py_max).unwrap());
+
+            // Year 0 exists in `time` but not in Python
+            assert!(min.previous_day().unwrap().into_pyobject(py).is_err());
+        })
+    }
+
+    #[test]
+    fn test_offset_datetime_into_py_local() {
+        Python::with_gil(|py| {
+            let datetime = Date::from_calendar_date(2021, Month::June, 9)
+                .unwrap()
+                .with_hms_micro(12, 34, 56, 789)
+                .unwrap()
+                .assume_offset(UtcOffset::from_hms(5, 30, 0).unwrap());
+            let local = offset_datetime_into_py_local(py, &datetime).unwrap();
+            assert!(!local.getattr("tzinfo").unwrap().is_none());
+            assert!(!local.call_method0("utcoffset").unwrap().is_none());
+            assert!(local.eq(datetime.into_pyobject(py).unwrap()).unwrap());
+            assert_eq!(local.extract::<OffsetDateTime>().unwrap(), datetime);
+
+            let python_local = datetime
+                .into_pyobject(py)
+                .unwrap()
+                .call_method1("astimezone", (py.None(),))
+                .unwrap();
+            assert!(local.eq(&python_local).unwrap());
+            assert_eq!(
+                local
+                    .call_method0("utcoffset")
+                    .unwrap()
+                    .extract::<Duration>()
+                    .unwrap(),
+                python_local
+                    .call_method0("utcoffset")
+                    .unwrap()
+                    .extract::<Duration>()
+                    .unwrap()
+            );
+        })
+    }
+
+    #[test]
+    fn test_duration_into_pydelta_clamped() {
+        Python::with_gil(|py| {
+            let (min, max) = (Duration::seconds(1), Duration::hours(24));
+            let clamp = |duration: Duration| -> (Duration, bool) {
+                let (delta, clamped) =
+                    duration_into_pydelta_clamped(py, duration, min, max).unwrap();
+                (delta.extract().unwrap(), clamped)
+            };
+            assert_eq!(clamp(Duration::minutes(5)), (Duration::minutes(5), false));
+            assert_eq!(clamp(min), (min, false));
+            assert_eq!(clamp(max), (max, false));
+            assert_eq!(clamp(Duration::hours(48)), (max, true));
+            assert_eq!(clamp(max + Duration::nanoseconds(1)), (max, true));
+            assert_eq!(clamp(Duration::ZERO), (min, true));
+            assert_eq!(clamp(Duration::days(-3)), (min, true));
+            assert_eq!(clamp(Duration::MAX), (max, true));
+            assert_eq!(clamp(Duration::MIN), (min, true));
+
+            // A degenerate range clamps everything else to a single value
+            let (delta, clamped) =
+                duration_into_pydelta_clamped(py, Duration::ZERO, max, max).unwrap();
+            assert_eq!((delta.extract::<Duration>().unwrap(), clamped), (max, true));
+
+            let err = duration_into_pydelta_clamped(py, Duration::ZERO, max, min).unwrap_err();
+            assert!(err.is_instance_of::<PyValueError>(py));
+            assert_eq!(
+                err.value(py).to_string(),
+                format!("min {} is greater than max {}", max, min)
+            );
+        })
+    }
+
+    #[test]
+    fn test_offset_datetime_from_dateutil_tz() {
+        Python::with_gil(|py| {
+            // dateutil is optional, so only test against it where it's installed
+            let gettz = match py.import("dateutil.tz") {
+                Ok(tz) => tz.getattr("gettz").unwrap(),
+                Err(_) => return,
+            };
+            let paris = gettz.call1(("Europe/Paris",)).unwrap();
+            assert!(!paris.is_none(), "no time zone data for Europe/Paris");
+
+            for (month, hours) in [(1, 1), (7, 2)] {
+                let py_datetime =
+                    new_py_datetime_ob(py, "datetime", (2021, month, 9, 12, 34, 56, 789, &paris));
+                let datetime: OffsetDateTime = py_datetime.extract().unwrap();
+                assert_eq!(datetime.offset(), UtcOffset::from_hms(hours, 0, 0).unwrap());
+                assert_eq!(
+                    datetime,
+                    Date::from_calendar_date(2021, Month::try_from(month).unwrap(), 9)
+                        .unwrap()
+                        .with_hms_micro(12, 34, 56, 789)
+                        .unwrap()
+                        .assume_offset(UtcOffset::from_hms(hours, 0, 0).unwrap())
+                );
+            }
+        })
+    }
+
+    #[test]
+    fn test_offset_datetime_replace_tzinfo() {
+        Python::with_gil(|py| {
+            let datetime = Date::from_calendar_date(2021, Month::June, 9)
+                .unwrap()
+                .with_hms_micro(12, 34, 56, 789)
+                .unwrap();
+            let dt = datetime.assume_utc().into_pyobject(py).unwrap();
+            let offset = UtcOffset::from_hms(5, 30, 0).unwrap();
+            let tz = offset.into_pyobject(py).unwrap();
+
+            let replaced = offset_datetime_replace_tzinfo(py, dt.as_any(), Some(&tz)).unwrap();
+            let extracted: OffsetDateTime = replaced.extract().unwrap();
+            // Same wall clock, different offset and thus a different instant
+            assert_eq!(extracted, datetime.assume_offset(offset));
+            assert_eq!(
+                PrimitiveDateTime::new(extracted.date(), extracted.time()),
+                datetime
+            );
+            assert!(!replaced.eq(&dt).unwrap());
+
+            // Unlike converting to the same instant
+            let converted: OffsetDateTime = dt
+                .call_method1("astimezone", (&tz,))
+                .unwrap()
+                .extract()
+                .unwrap();
+            assert_eq!(converted, datetime.assume_utc());
+            assert_ne!(converted.time(), extracted.time());
+
+            let naive = offset_datetime_replace_tzinfo(py, dt.as_any(), None).unwrap();
+            assert!(naive.getattr("tzinfo").unwrap().is_none());
+            assert_eq!(naive.extract::<PrimitiveDateTime>().unwrap(), datetime);
+        })
+    }
+
+    #[test]
+    fn test_date_yday() {
+        Python::with_gil(|py| {
+            for (year, month, day, yday) in [
+                (2021, Month::January, 1, 1),
+                (2021, Month::June, 9, 160),
+                (2021, Month::December, 31, 365),
+                (2024, Month::March, 1, 61),
+                (2024, Month::December, 31, 366),
+                (1, Month::January, 1, 1),
+                (9999, Month::December, 31, 365),
+            ] {
+                let date = Date::from_calendar_date(year, month, day).unwrap();
+                let py_yday = date_to_py_yday(py, date).unwrap();
+                assert_eq!(py_yday.extract::<u16>().unwrap(), yday);
+                let tm_yday = date
+                    .into_pyobject(py)
+                    .unwrap()
+                    .call_method0("timetuple")
+                    .unwrap()
+                    .getattr("tm_yday")
+                    .unwrap();
+                assert!(py_yday.as_any().eq(tm_yday).unwrap());
+                assert_eq!(date_from_year_and_yday(py, year, yday).unwrap(), date);
+            }
+
+            for (year, yday, days) in [(2021, 366, 365), (2024, 367, 366), (2024, 0, 366)] {
+                let err = date_from_year_and_yday(py, year, yday).unwrap_err();
+                assert!(err.is_instance_of::<PyValueError>(py));
+                assert_eq!(
+                    err.value(py).to_string(),
+                    format!(
+                        "day of year {} is out of range for year {}, must be in 1..={}",
+                        yday, year, days
+                    )
+                );
+            }
+            // Years `time` may support but Python doesn't
+            for year in [-1, 0, 10_000] {
+                let err = date_from_year_and_yday(py, year, 1).unwrap_err();
+                assert!(err.is_instance_of::<PyValueError>(py));
+                assert!(err.value(py).to_string().starts_with(&format!(
+                    "year {} is out of range for Python's datetime",
+                    year
+                )));
+                // Year 10000 needs the `large-dates` feature
+                if let Ok(date) = Date::from_calendar_date(year, Month::January, 1) {
+                    assert!(date_to_py_yday(py, date)
+                        .unwrap_err()
+                        .is_instance_of::<PyValueError>(py));
+                }
+            }
+        })
+    }
+
+    #[test]
+    #[cfg(not(Py_LIMITED_API))]
+    fn test_py_date_to_naive_date() {
+        Python::with_gil(|py| {
+            for year in [1, 1900, 2000, 2023, 2024, 9999] {
+                let mut date = Date::from_calendar_date(year, Month::January, 1).unwrap();
+                while date.year() == year {
+                    let py_date = PyDate::new(py, year, date.month().into(), date.day()).unwrap();
+                    assert_eq!(py_date_to_naive_date(&py_date).unwrap(), date);
+                    date = match date.next_day() {
+                        Some(next) => next,
+                        None => break,
+                    };
+                }
+            }
+        })
+    }
+
+    #[test]
+    fn test_extract_primitive_datetimes_from_pandas_index() {
+        Python::with_gil(|py| {
+            // pandas is optional, so only test against it where it's installed
+            let pandas = match py.import("pandas") {
+                Ok(pandas) => pandas,
+                Err(_) => return,
+            };
+            let index = pandas
+                .getattr("DatetimeIndex")
+                .unwrap()
+                .call1((vec![
+                    "2021-06-09T12:34:56.123456789",
+                    "1969-12-31T23:59:59.999999999",
+                    "2262-04-11T23:47:16.854775807",
+                ],))
+                .unwrap();
+            let expected = vec![
+                Date::from_calendar_date(2021, Month::June, 9)
+                    .unwrap()
+                    .with_hms_nano(12, 34, 56, 123_456_789)
+                    .unwrap(),
+                Date::from_calendar_date(1969, Month::December, 31)
+                    .unwrap()
+                    .with_hms_nano(23, 59, 59, 999_999_999)
+                    .unwrap(),
+                Date::from_calendar_date(2262, Month::April, 11)
+                    .unwrap()
+                    .with_hms_nano(23, 47, 16, 854_775_807)
+                    .unwrap(),
+            ];
+            assert_eq!(
+                extract_primitive_datetimes_from_index(&index).unwrap(),
+                expected
+            );
+            // The same as extracting the `Timestamp`s one by one
+            let timestamps = index.call_method0("tolist").unwrap();
+            assert_eq!(
+                extract_primitive_datetimes_from_index(&timestamps).unwrap(),
+                expected
+            );
+
+            // pandas>=2.0 also has coarser units
+            if index.hasattr("as_unit").unwrap() {
+                let floored = index.call_method1("floor", ("s",)).unwrap();
+                let expected: Vec<PrimitiveDateTime> = expected
+                    .iter()
+                    .map(|dt| dt.replace_nanosecond(0).unwrap())
+                    .collect();
+                for unit in ["s", "ms", "us", "ns"] {
+                    let index = floored.call_method1("as_unit", (unit,)).unwrap();
+                    assert_eq!(
+                        extract_primitive_datetimes_from_index(&index).unwrap(),
+                        expected,
+                        "{}",
+                        unit
+                    );
+                }
+            }
+        })
+    }
+
     fn new_py_datetime_ob<'py, A>(py: Python<'py>, name: &str, args: A) -> Bound<'py, PyAny>
     where
         A: IntoPyObject<'py, Target = PyTuple>,
@@ -0,0 +1,141 @@
+//! The registry of `set_datetime_class` is shared by all threads, so it is tested in its own test
+//! binary where it can't affect the conversions of concurrently running tests.
+
+use pyo3::time::{set_datetime_class, DatetimeClass};
+use pyo3::types::{IntoPyDict, PyType};
+use pyo3::{ffi, prelude::*};
+use time::{Date, Month, OffsetDateTime, PrimitiveDateTime, Time, UtcOffset};
+
+fn date() -> Date {
+    Date::from_calendar_date(2024, Month::February, 29).unwrap()
+}
+
+fn time() -> Time {
+    Time::from_hms_micro(12, 34, 56, 789).unwrap()
+}
+
+/// Asserts the classes produced by the conversions and that the results extract back.
+fn assert_types(
+    py: Python<'_>,
+    date_type: &Bound<'_, PyAny>,
+    time_type: &Bound<'_, PyAny>,
+    datetime_type: &Bound<'_, PyAny>,
+) {
+    let datetime = PrimitiveDateTime::new(date(), time());
+    let offset_datetime = datetime.assume_offset(UtcOffset::from_hms(5, 30, 0).unwrap());
+
+    let py_date = date().into_pyobject(py).unwrap();
+    assert!(py_date.get_type().is(date_type));
+    assert_eq!(py_date.extract::<Date>().unwrap(), date());
+    let py_time = time().into_pyobject(py).unwrap();
+    assert!(py_time.get_type().is(time_type));
+    assert_eq!(py_time.extract::<Time>().unwrap(), time());
+    let py_datetime = datetime.into_pyobject(py).unwrap();
+    assert!(py_datetime.get_type().is(datetime_type));
+    assert_eq!(
+        py_datetime.extract::<PrimitiveDateTime>().unwrap(),
+        datetime
+    );
+    let py_datetime = offset_datetime.into_pyobject(py).unwrap();
+    assert!(py_datetime.get_type().is(datetime_type));
+    assert_eq!(
+        py_datetime.extract::<OffsetDateTime>().unwrap(),
+        offset_datetime
+    );
+}
+
+#[test]
+fn test_set_datetime_class() {
+    Python::with_gil(|py| {
+        let globals = [("datetime", py.import("datetime").unwrap())]
+            .into_py_dict(py)
+            .unwrap();
+        py.run(
+            ffi::c_str!(
+                r#"
+class MyDate(datetime.date):
+    pass
+
+class MyTime(datetime.time):
+    pass
+
+class MyDateTime(datetime.datetime):
+    pass
+"#
+            ),
+            Some(&globals),
+            None,
+        )
+        .unwrap();
+        let class = |name: &str| -> Bound<'_, PyType> {
+            globals
+                .get_item(name)
+                .unwrap()
+                .unwrap()
+                .downcast_into()
+                .unwrap()
+        };
+        let (my_date, my_time, my_datetime) =
+            (class("MyDate"), class("MyTime"), class("MyDateTime"));
+
+        let datetime_module = py.import("datetime").unwrap();
+        let builtin = |name: &str| datetime_module.getattr(name).unwrap();
+        let (builtin_date, builtin_time, builtin_datetime) =
+            (builtin("date"), builtin("time"), builtin("datetime"));
+        assert_types(py, &builtin_date, &builtin_time, &builtin_datetime);
+
+        assert!(set_datetime_class(py, DatetimeClass::Date, Some(&my_date))
+            .unwrap()
+            .is_none());
+        assert!(set_datetime_class(py, DatetimeClass::Time, Some(&my_time))
+            .unwrap()
+            .is_none());
+        assert!(
+            set_datetime_class(py, DatetimeClass::DateTime, Some(&my_datetime))
+                .unwrap()
+                .is_none()
+        );
+        assert_types(py, &my_date, &my_time, &my_datetime);
+
+        // The registered classes are also used on other threads
+        let (date_type, time_type, datetime_type) = (
+            my_date.clone().unbind(),
+            my_time.clone().unbind(),
+            my_datetime.clone().unbind(),
+        );
+        py.allow_threads(|| {
+            std::thread::spawn(move || {
+                Python::with_gil(|py| {
+                    assert_types(
+                        py,
+                        date_type.bind(py),
+                        time_type.bind(py),
+                        datetime_type.bind(py),
+                    )
+                })
+            })
+            .join()
+            .unwrap()
+        });
+
+        // Classes which aren't subclasses of the builtin are rejected
+        let err = set_datetime_class(py, DatetimeClass::DateTime, Some(&my_date)).unwrap_err();
+        assert!(err.is_instance_of::<pyo3::exceptions::PyTypeError>(py));
+        assert_eq!(
+            err.value(py).to_string(),
+            "<class 'MyDate'> is not a subclass of datetime.datetime"
+        );
+        assert_types(py, &my_date, &my_time, &my_datetime);
+
+        // Restoring the builtins returns the registered classes
+        for (kind, cls) in [
+            (DatetimeClass::Date, &my_date),
+            (DatetimeClass::Time, &my_time),
+            (DatetimeClass::DateTime, &my_datetime),
+        ] {
+            let previous = set_datetime_class(py, kind, None).unwrap().unwrap();
+            assert!(previous.bind(py).is(cls));
+        }
+        assert_types(py, &builtin_date, &builtin_time, &builtin_datetime);
+    })
+}